@@ -7,6 +7,7 @@ use anyhow::Context;
 use cs2::{
     CEntityIdentityEx,
     CS2Handle,
+    CSchemaClassBinding,
 };
 use cs2_schema_declaration::Ptr;
 use cs2_schema_generated::cs2::client::CEntityIdentity;
@@ -16,8 +17,14 @@ pub struct ClassNameCache {
 
     lookup: BTreeMap<u64, String>,
     reverse_lookup: BTreeMap<String, u64>,
+
+    /// Names of all base classes (excluding the class itself)
+    base_classes: BTreeMap<u64, Vec<String>>,
 }
 
+/// Max inheritance depth to protect against invalid/circular class bindings
+const MAX_INHERITANCE_DEPTH: usize = 32;
+
 impl ClassNameCache {
     pub fn new(cs2: Arc<CS2Handle>) -> Self {
         Self {
@@ -25,6 +32,7 @@ impl ClassNameCache {
 
             lookup: Default::default(),
             reverse_lookup: Default::default(),
+            base_classes: Default::default(),
         }
     }
 
@@ -53,11 +61,51 @@ impl ClassNameCache {
             .cs2
             .read_string(&[address + 0x28, 0x08, 0x00], Some(32))?;
 
+        let base_classes = self.read_base_classes(address)?;
+
         self.lookup.insert(address, class_name.clone());
         self.reverse_lookup.insert(class_name, address);
+        self.base_classes.insert(address, base_classes);
         Ok(())
     }
 
+    fn read_base_classes(&self, class_info: u64) -> anyhow::Result<Vec<String>> {
+        let mut binding = self
+            .cs2
+            .reference_schema::<CSchemaClassBinding>(&[class_info + 0x28, 0x00])?;
+
+        let mut base_classes = Vec::new();
+        while base_classes.len() < MAX_INHERITANCE_DEPTH {
+            let base_class = binding.base_class()?;
+            if base_class.is_null()? {
+                break;
+            }
+
+            binding = base_class
+                .reference_schema()?
+                .class_binding()?
+                .reference_schema()?;
+            base_classes.push(binding.name()?.read_string()?);
+        }
+
+        Ok(base_classes)
+    }
+
+    /// Check if the class is the given class or inherits from it.
+    /// Returns false if the class is unknown.
+    pub fn class_is_a(&self, class_info: &Ptr<()>, base: &str) -> anyhow::Result<bool> {
+        let address = class_info.address()?;
+        if self.lookup.get(&address).map(|name| name == base) == Some(true) {
+            return Ok(true);
+        }
+
+        Ok(self
+            .base_classes
+            .get(&address)
+            .map(|base_classes| base_classes.iter().any(|name| name == base))
+            .unwrap_or(false))
+    }
+
     pub fn lookup(&self, class_info: &Ptr<()>) -> anyhow::Result<Option<&String>> {
         let address = class_info.address()?;
         Ok(self.lookup.get(&address))
@@ -0,0 +1,38 @@
+use crate::UpdateContext;
+
+/// Performance related values for diagnostics.
+/// Values which could not be read (or are implausible) are None.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PerfInfo {
+    pub server_tickrate: Option<f32>,
+    pub client_fps: Option<f32>,
+}
+
+fn plausible(value: f32, min: f32, max: f32) -> Option<f32> {
+    if value.is_finite() && value >= min && value <= max {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+pub fn read_performance_info(ctx: &UpdateContext) -> PerfInfo {
+    let server_tickrate = ctx
+        .globals
+        .two_tick_time()
+        .ok()
+        .filter(|time| *time > 0.0)
+        .and_then(|time| plausible(2.0 / time, 1.0, 1024.0));
+
+    let client_fps = ctx
+        .globals
+        .frame_time()
+        .ok()
+        .filter(|time| *time > 0.0)
+        .and_then(|time| plausible(1.0 / time, 1.0, 10_000.0));
+
+    PerfInfo {
+        server_tickrate,
+        client_fps,
+    }
+}
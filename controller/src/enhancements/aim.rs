@@ -2,6 +2,7 @@ use anyhow::Context;
 use valthrun_kernel_interface::MouseState;
 
 use super::Enhancement;
+use crate::game_rules::is_freeze_period;
 
 pub struct AntiAimPunsh {
     mouse_sensitivity: f32,
@@ -31,6 +32,10 @@ impl Enhancement for AntiAimPunsh {
             return Ok(());
         }
 
+        if is_freeze_period(ctx)? {
+            return Ok(());
+        }
+
         let local_controller = ctx.cs2_entities.get_local_player_controller()?;
         if local_controller.is_null()? {
             return Ok(());
@@ -1,17 +1,32 @@
-use std::ffi::CStr;
-
 use anyhow::Context;
-use cs2::CEntityIdentityEx;
-use cs2_schema_generated::cs2::client::C_PlantedC4;
+use cs2::{
+    read_origin,
+    read_pawn_origin,
+    CEntityIdentityEx,
+};
+use cs2_schema_generated::cs2::client::{
+    CCSPlayer_ItemServices,
+    C_PlantedC4,
+    C_C4,
+};
 use obfstr::obfstr;
+use serde::{
+    Deserialize,
+    Serialize,
+};
 
-use super::Enhancement;
+use super::{
+    read_local_pawn,
+    Enhancement,
+};
 use crate::{
+    spawn_points::TEAM_COUNTER_TERRORIST,
     utils::ImguiUiEx,
+    weapon::WeaponId,
     UpdateContext,
 };
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BombDefuser {
     /// Totoal time remaining for a successfull bomb defuse
     pub time_remaining: f32,
@@ -20,25 +35,79 @@ pub struct BombDefuser {
     pub player_name: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct C4Info {
     /// Planted bomb site
     /// 0 = A
     /// 1 = B
-    bomb_site: u8,
+    /// None if the bomb has not been planted
+    bomb_site: Option<u8>,
 
     /// Current state of the C4
     state: C4State,
+
+    /// The local player owns a defuse kit.
+    /// None if the local player can not defuse the bomb (not an alive counter-terrorist).
+    local_defuse_kit: Option<bool>,
 }
 
-#[derive(Debug)]
+impl C4Info {
+    /// Returns true if the local player could start defusing now
+    /// and would finish before the bomb detonates.
+    /// Always false if the local player is not an alive counter-terrorist.
+    pub fn local_can_defuse(&self) -> bool {
+        let local_has_defuse_kit = match self.local_defuse_kit {
+            Some(value) => value,
+            None => return false,
+        };
+
+        match &self.state {
+            C4State::Active {
+                time_detonation, ..
+            } => {
+                let defuse_time = if local_has_defuse_kit {
+                    DEFUSE_TIME_KIT
+                } else {
+                    DEFUSE_TIME_NO_KIT
+                };
+
+                *time_detonation > defuse_time
+            }
+            C4State::Dropped { .. } | C4State::Detonated | C4State::Defused => false,
+        }
+    }
+
+    /// Returns the remaining time until detonation relative to the total timer length in [0.0; 1.0].
+    pub fn time_remaining_fraction(&self) -> Option<f32> {
+        match &self.state {
+            C4State::Active {
+                time_detonation,
+                timer_length,
+                ..
+            } => Some((time_detonation / timer_length).clamp(0.0, 1.0)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum C4State {
+    /// Bomb has been dropped and is lying on the ground
+    Dropped { pos: [f32; 3] },
+
     /// Bomb is currently actively ticking
     Active {
         /// Time remaining (in seconds) until detonation
         time_detonation: f32,
 
+        /// Total time (in seconds) from planting until detonation
+        timer_length: f32,
+
         /// Current bomb defuser
         defuse: Option<BombDefuser>,
+
+        /// Defusing this plant in time requires a defuse kit
+        requires_kit: bool,
     },
 
     /// Bomb has detonated
@@ -48,17 +117,350 @@ pub enum C4State {
     Defused,
 }
 
+/// Default C4 timer (in seconds) used when the actual timer could not be read
+const DEFAULT_C4_TIMER: f32 = 40.0;
+
+/// Time (in seconds) required to defuse the bomb with a defuse kit
+const DEFUSE_TIME_KIT: f32 = 5.0;
+
+/// Time (in seconds) required to defuse the bomb without a defuse kit
+const DEFUSE_TIME_NO_KIT: f32 = 10.0;
+
+/// Time (in seconds) required to plant the bomb
+const C4_ARM_TIME: f32 = 3.0;
+
+/// Maximum plausible distance (in units) between the defuser and the bomb
+const DEFUSER_MAX_DISTANCE: f32 = 128.0;
+
+/// Returns true if the local player pawn currently owns a defuse kit.
+/// Returns None if the local player is not an alive counter-terrorist.
+pub fn read_local_defuse_kit(ctx: &UpdateContext) -> anyhow::Result<Option<bool>> {
+    let local_pawn = match read_local_pawn(ctx)? {
+        Some(pawn) => pawn,
+        None => return Ok(None),
+    };
+
+    if local_pawn.m_iTeamNum()? != TEAM_COUNTER_TERRORIST {
+        return Ok(None);
+    }
+
+    match local_pawn
+        .m_pItemServices()?
+        .cast::<CCSPlayer_ItemServices>()
+        .try_reference_schema()?
+    {
+        Some(item_services) => Ok(Some(item_services.m_bHasDefuser()?)),
+        None => Ok(Some(false)),
+    }
+}
+
+/// Read the plant progress (0..1) of the local player.
+/// Returns None if the local player is currently not planting the bomb.
+pub fn local_plant_progress(ctx: &UpdateContext) -> anyhow::Result<Option<f32>> {
+    let local_pawn = match read_local_pawn(ctx)? {
+        Some(pawn) => pawn,
+        None => return Ok(None),
+    };
+
+    let weapon = match local_pawn.m_pClippingWeapon()?.try_reference_schema()? {
+        Some(weapon) => weapon,
+        None => return Ok(None),
+    };
+
+    let weapon_id = weapon
+        .m_AttributeManager()?
+        .m_Item()?
+        .m_iItemDefinitionIndex()?;
+    if weapon_id != WeaponId::C4.id() {
+        return Ok(None);
+    }
+
+    let bomb = local_pawn
+        .m_pClippingWeapon()?
+        .cast::<C_C4>()
+        .read_schema()?;
+    if !bomb.m_bStartedArming()? {
+        return Ok(None);
+    }
+
+    /* m_fArmedTime contains the game time at which the bomb will be planted */
+    let time_remaining = bomb.m_fArmedTime()?.m_Value()? - ctx.globals.cur_time()?;
+    Ok(Some((1.0 - time_remaining / C4_ARM_TIME).clamp(0.0, 1.0)))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BombOutcome {
+    Defused,
+    Detonated,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct BombResult {
+    pub outcome: BombOutcome,
+
+    /// For a defused bomb the time (in seconds) which was remaining until detonation.
+    /// For a detonated bomb the time (in seconds) the active defuser would have needed additionally
+    /// or zero if nobody has been defusing.
+    pub margin_seconds: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DefuseEvent {
+    /// A player started defusing the bomb
+    Started { player_name: String },
+
+    /// A player stopped defusing the bomb before it has been defused
+    Cancelled { player_name: String },
+}
+
+/// Tracks the bomb defuser across frames and emits defuse start/stop transitions.
+pub struct DefuseTracker {
+    defuser: Option<String>,
+}
+
+impl DefuseTracker {
+    pub fn new() -> Self {
+        Self { defuser: None }
+    }
+
+    /// Update the tracker with the current bomb state.
+    /// Returns the defuse events since the last update.
+    pub fn update(&mut self, bomb_state: Option<&C4Info>) -> Vec<DefuseEvent> {
+        let defuser = match bomb_state.map(|info| &info.state) {
+            Some(C4State::Active { defuse, .. }) => {
+                defuse.as_ref().map(|defuse| defuse.player_name.clone())
+            }
+            _ => {
+                /* bomb defused, detonated or a new round started */
+                self.defuser = None;
+                return Vec::new();
+            }
+        };
+
+        if defuser == self.defuser {
+            return Vec::new();
+        }
+
+        let mut events = Vec::with_capacity(2);
+        if let Some(player_name) = self.defuser.take() {
+            events.push(DefuseEvent::Cancelled { player_name });
+        }
+
+        if let Some(player_name) = &defuser {
+            events.push(DefuseEvent::Started {
+                player_name: player_name.clone(),
+            });
+        }
+
+        self.defuser = defuser;
+        events
+    }
+}
+
 pub struct BombInfo {
     bomb_state: Option<C4Info>,
+
+    defuse_tracker: DefuseTracker,
+    defuse_events: Vec<DefuseEvent>,
+
+    /// The last active state of the current plant: (time_detonation, time_defuse_remaining)
+    last_active_state: Option<(f32, Option<f32>)>,
+
+    bomb_result: Option<BombResult>,
+    bomb_result_consumed: bool,
 }
 
 impl BombInfo {
     pub fn new() -> Self {
-        Self { bomb_state: None }
+        Self {
+            bomb_state: None,
+
+            defuse_tracker: DefuseTracker::new(),
+            defuse_events: Vec::new(),
+
+            last_active_state: None,
+
+            bomb_result: None,
+            bomb_result_consumed: false,
+        }
     }
 
-    fn read_state(&self, ctx: &UpdateContext) -> anyhow::Result<Option<C4Info>> {
+    /// Returns the result of the current rounds bomb plant.
+    /// The result will only be returned once per round.
+    pub fn take_bomb_result(&mut self) -> Option<BombResult> {
+        if self.bomb_result_consumed {
+            return None;
+        }
+
+        let result = self.bomb_result?;
+        self.bomb_result_consumed = true;
+        Some(result)
+    }
+
+    /// Returns the defuse start/stop events of the last update.
+    pub fn take_defuse_events(&mut self) -> Vec<DefuseEvent> {
+        std::mem::take(&mut self.defuse_events)
+    }
+
+    fn update_bomb_result(&mut self) {
+        let state = match &self.bomb_state {
+            Some(C4Info {
+                state: C4State::Dropped { .. },
+                ..
+            })
+            | None => {
+                /* bomb not planted (yet) or the planted bomb has been removed which indicates a new round */
+                self.last_active_state = None;
+                self.bomb_result = None;
+                self.bomb_result_consumed = false;
+                return;
+            }
+            Some(info) => &info.state,
+        };
+
+        match state {
+            C4State::Dropped { .. } => {}
+            C4State::Active {
+                time_detonation,
+                defuse,
+                ..
+            } => {
+                self.last_active_state = Some((
+                    *time_detonation,
+                    defuse.as_ref().map(|defuse| defuse.time_remaining),
+                ));
+            }
+            C4State::Defused | C4State::Detonated => {
+                if self.bomb_result.is_some() {
+                    /* result already captured */
+                    return;
+                }
+
+                let (time_detonation, time_defuse) = self.last_active_state.unwrap_or_default();
+                self.bomb_result = Some(match state {
+                    C4State::Defused => BombResult {
+                        outcome: BombOutcome::Defused,
+                        margin_seconds: time_detonation.max(0.0),
+                    },
+                    _ => BombResult {
+                        outcome: BombOutcome::Detonated,
+                        margin_seconds: time_defuse
+                            .map(|time_defuse| (time_defuse - time_detonation).max(0.0))
+                            .unwrap_or(0.0),
+                    },
+                });
+            }
+        }
+    }
+
+    /// Evaluate the state of a planted bomb at the given game time.
+    /// The name of the current defuser is resolved by `read_defuser`, which returns None
+    /// if the defuser is not plausible.
+    /// Returns None if the bomb has not been activated yet.
+    fn read_planted_state(
+        bomb: &C_PlantedC4,
+        current_time: f32,
+        read_defuser: impl FnOnce(&C_PlantedC4) -> anyhow::Result<Option<String>>,
+    ) -> anyhow::Result<Option<C4Info>> {
+        if !bomb.m_bC4Activated()? {
+            return Ok(None);
+        }
+
+        let bomb_site = Some(bomb.m_nBombSite()? as u8);
+        if bomb.m_bBombDefused()? {
+            return Ok(Some(C4Info {
+                bomb_site,
+                state: C4State::Defused,
+                local_defuse_kit: None,
+            }));
+        }
+
+        let time_blow = bomb.m_flC4Blow()?.m_Value()?;
+
+        if time_blow <= current_time {
+            return Ok(Some(C4Info {
+                bomb_site,
+                state: C4State::Detonated,
+                local_defuse_kit: None,
+            }));
+        }
+
+        let time_detonation = time_blow - current_time;
+        let timer_length = bomb
+            .m_flTimerLength()
+            .ok()
+            .filter(|length| length.is_finite() && *length > 0.0)
+            .unwrap_or(DEFAULT_C4_TIMER);
+
+        /*
+         * The CS2 schema does not expose a dedicated "defuse needs kit" field on C_PlantedC4.
+         * Infer it from the remaining time instead: A kit is required once a kit-less defuse can no longer finish.
+         */
+        let requires_kit = time_detonation < DEFUSE_TIME_NO_KIT;
+
+        let defusing = if bomb.m_bBeingDefused()? {
+            let time_defuse = bomb.m_flDefuseCountDown()?.m_Value()?;
+            read_defuser(bomb)?.map(|player_name| BombDefuser {
+                time_remaining: time_defuse - current_time,
+                player_name,
+            })
+        } else {
+            None
+        };
+
+        Ok(Some(C4Info {
+            bomb_site,
+            state: C4State::Active {
+                time_detonation,
+                timer_length,
+                defuse: defusing,
+                requires_kit,
+            },
+            local_defuse_kit: None,
+        }))
+    }
+
+    /// Read the player name of the current bomb defuser.
+    /// Returns None if the defuser is not plausible.
+    fn read_defuser_name(
+        ctx: &UpdateContext,
+        bomb: &C_PlantedC4,
+    ) -> anyhow::Result<Option<String>> {
+        let handle_defuser = bomb.m_hBombDefuser()?;
+        let defuser = ctx
+            .cs2_entities
+            .get_by_handle(&handle_defuser)?
+            .with_context(|| obfstr!("missing bomb defuser player pawn").to_string())?
+            .entity()?
+            .reference_schema()?;
+
+        let bomb_position = read_origin(bomb)?;
+        let defuser_position = read_pawn_origin(&defuser)?;
+
+        let defuser_plausible = match (bomb_position, defuser_position) {
+            (Some(bomb_position), Some(defuser_position)) => {
+                let bomb_position = nalgebra::Vector3::<f32>::from_column_slice(&bomb_position);
+                let defuser_position =
+                    nalgebra::Vector3::<f32>::from_column_slice(&defuser_position);
+                (defuser_position - bomb_position).norm() <= DEFUSER_MAX_DISTANCE
+            }
+            _ => false,
+        };
+        if !defuser_plausible {
+            /* the defuser handle is most likely stale or the defuser is still being created */
+            return Ok(None);
+        }
+
+        let defuser_name = ctx
+            .player_name_cache
+            .lookup(ctx.cs2_entities, &defuser.m_hController()?)?
+            .with_context(|| obfstr!("missing bomb defuser controller").to_string())?;
+        Ok(Some(defuser_name))
+    }
+
+    pub fn read_state(ctx: &UpdateContext) -> anyhow::Result<Option<C4Info>> {
         let entities = ctx.cs2_entities.all_identities();
+        let mut dropped_bomb = None;
 
         for entity_identity in entities.iter() {
             let class_name = ctx
@@ -66,85 +468,59 @@ impl BombInfo {
                 .lookup(&entity_identity.entity_class_info()?)
                 .context("class name")?;
 
-            if !class_name
-                .map(|name| name == "C_PlantedC4")
-                .unwrap_or(false)
-            {
-                /* Entity isn't the bomb. */
-                continue;
+            match class_name.map(String::as_str) {
+                Some("C_PlantedC4") => {}
+                Some("C_C4") => {
+                    let bomb = entity_identity
+                        .entity_ptr::<C_C4>()?
+                        .read_schema()
+                        .context("c4 schema")?;
+
+                    if bomb.m_hOwnerEntity()?.is_valid() {
+                        /* Bomb is carried by a player */
+                        continue;
+                    }
+
+                    let pos = match read_origin(&bomb)? {
+                        Some(pos) => pos,
+                        /* entity is still being created */
+                        None => continue,
+                    };
+                    dropped_bomb = Some(C4Info {
+                        bomb_site: None,
+                        state: C4State::Dropped { pos },
+                        local_defuse_kit: None,
+                    });
+                    continue;
+                }
+                _ => {
+                    /* Entity isn't the bomb. */
+                    continue;
+                }
             }
 
             let bomb = entity_identity
                 .entity_ptr::<C_PlantedC4>()?
                 .read_schema()
                 .context("bomb schame")?;
-            if !bomb.m_bC4Activated()? {
-                /* This bomb hasn't been activated (yet) */
-                continue;
-            }
-
-            let bomb_site = bomb.m_nBombSite()? as u8;
-            if bomb.m_bBombDefused()? {
-                return Ok(Some(C4Info {
-                    bomb_site,
-                    state: C4State::Defused,
-                }));
-            }
-
-            let time_blow = bomb.m_flC4Blow()?.m_Value()?;
-
-            if time_blow <= ctx.globals.time_2()? {
-                return Ok(Some(C4Info {
-                    bomb_site,
-                    state: C4State::Detonated,
-                }));
-            }
-
-            let is_defusing = bomb.m_bBeingDefused()?;
-            let defusing = if is_defusing {
-                let time_defuse = bomb.m_flDefuseCountDown()?.m_Value()?;
-
-                let handle_defuser = bomb.m_hBombDefuser()?;
-                let defuser = ctx
-                    .cs2_entities
-                    .get_by_handle(&handle_defuser)?
-                    .with_context(|| obfstr!("missing bomb defuser player pawn").to_string())?
-                    .entity()?
-                    .reference_schema()?;
-
-                let defuser_controller = defuser.m_hController()?;
-                let defuser_controller = ctx
-                    .cs2_entities
-                    .get_by_handle(&defuser_controller)?
-                    .with_context(|| obfstr!("missing bomb defuser controller").to_string())?
-                    .entity()?
-                    .reference_schema()?;
-
-                let defuser_name =
-                    CStr::from_bytes_until_nul(&defuser_controller.m_iszPlayerName()?)
-                        .ok()
-                        .map(CStr::to_string_lossy)
-                        .unwrap_or("Name Error".into())
-                        .to_string();
-
-                Some(BombDefuser {
-                    time_remaining: time_defuse - ctx.globals.time_2()?,
-                    player_name: defuser_name,
-                })
-            } else {
-                None
-            };
 
-            return Ok(Some(C4Info {
-                bomb_site,
-                state: C4State::Active {
-                    time_detonation: time_blow - ctx.globals.time_2()?,
-                    defuse: defusing,
-                },
-            }));
+            let mut bomb_info =
+                match Self::read_planted_state(&bomb, ctx.globals.time_2()?, |bomb| {
+                    Self::read_defuser_name(ctx, bomb)
+                })? {
+                    Some(bomb_info) => bomb_info,
+                    /* This bomb hasn't been activated (yet) */
+                    None => continue,
+                };
+
+            if let C4State::Active { .. } = &bomb_info.state {
+                bomb_info.local_defuse_kit = read_local_defuse_kit(ctx)?;
+            }
+
+            return Ok(Some(bomb_info));
         }
 
-        return Ok(None);
+        return Ok(dropped_bomb);
     }
 }
 
@@ -160,7 +536,10 @@ impl Enhancement for BombInfo {
             return Ok(());
         }
 
-        self.bomb_state = self.read_state(ctx)?;
+        self.bomb_state = Self::read_state(ctx)?;
+        self.update_bomb_result();
+
+        self.defuse_events = self.defuse_tracker.update(self.bomb_state.as_ref());
         Ok(())
     }
 
@@ -182,6 +561,8 @@ impl Enhancement for BombInfo {
         let group = ui.begin_group();
 
         let line_count = match &bomb_info.state {
+            C4State::Dropped { .. } => 1,
+            C4State::Active { .. } if bomb_info.local_defuse_kit.is_some() => 4,
             C4State::Active { .. } => 3,
             C4State::Defused | C4State::Detonated => 2,
         };
@@ -194,18 +575,34 @@ impl Enhancement for BombInfo {
             + 0_f32.max((ui.io().display_size[1] * PLAYER_AVATAR_SIZE - text_height) / 2.0);
 
         ui.set_cursor_pos([offset_x, offset_y]);
-        ui.text(&format!(
-            "Bomb planted {}",
-            if bomb_info.bomb_site == 0 { "A" } else { "B" }
-        ));
+        match bomb_info.bomb_site {
+            Some(bomb_site) => ui.text(&format!(
+                "Bomb planted {}",
+                if bomb_site == 0 { "A" } else { "B" }
+            )),
+            None => ui.text("Bomb dropped"),
+        }
 
         match &bomb_info.state {
+            C4State::Dropped { .. } => {}
             C4State::Active {
                 time_detonation,
+                timer_length,
                 defuse,
+                requires_kit,
             } => {
                 ui.set_cursor_pos_x(offset_x);
-                ui.text(&format!("Time: {:.3}", time_detonation));
+                if *requires_kit {
+                    ui.text(&format!(
+                        "Time: {:.3} / {:.0} (kit required)",
+                        time_detonation, timer_length
+                    ));
+                } else {
+                    ui.text(&format!(
+                        "Time: {:.3} / {:.0}",
+                        time_detonation, timer_length
+                    ));
+                }
                 if let Some(defuse) = defuse.as_ref() {
                     let color = if defuse.time_remaining > *time_detonation {
                         [0.79, 0.11, 0.11, 1.0]
@@ -225,10 +622,26 @@ impl Enhancement for BombInfo {
                     ui.set_cursor_pos_x(offset_x);
                     ui.text("Not defusing");
                 }
+
+                if bomb_info.local_defuse_kit.is_some() {
+                    ui.set_cursor_pos_x(offset_x);
+                    if bomb_info.local_can_defuse() {
+                        ui.text_colored([0.11, 0.79, 0.26, 1.0], "You can defuse in time");
+                    } else {
+                        ui.text_colored([0.79, 0.11, 0.11, 1.0], "Too late for you to defuse");
+                    }
+                }
             }
             C4State::Defused => {
                 ui.set_cursor_pos_x(offset_x);
-                ui.text("Bomb has been defused");
+                if let Some(result) = &self.bomb_result {
+                    ui.text(&format!(
+                        "Bomb has been defused ({:.3}s left)",
+                        result.margin_seconds
+                    ));
+                } else {
+                    ui.text("Bomb has been defused");
+                }
             }
             C4State::Detonated => {
                 ui.set_cursor_pos_x(offset_x);
@@ -239,3 +652,375 @@ impl Enhancement for BombInfo {
         group.end();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use cs2_schema_declaration::VecMemoryDriver;
+    use cs2_schema_generated::cs2::client::C_PlantedC4;
+
+    use super::{
+        BombDefuser,
+        BombInfo,
+        BombOutcome,
+        C4Info,
+        C4State,
+        DefuseEvent,
+        DefuseTracker,
+    };
+
+    const BASE_ADDRESS: u64 = 0x10000;
+    const BUFFER_SIZE: usize = 0x1000;
+
+    /* schema offsets */
+    const OFFSET_BOMB_SITE: usize = 0xE84;
+    const OFFSET_C4_BLOW: usize = 0xEB0;
+    const OFFSET_TIMER_LENGTH: usize = 0xEB8;
+    const OFFSET_BEING_DEFUSED: usize = 0xEBC;
+    const OFFSET_C4_ACTIVATED: usize = 0xEC8;
+    const OFFSET_DEFUSE_COUNT_DOWN: usize = 0xED0;
+    const OFFSET_BOMB_DEFUSED: usize = 0xED4;
+
+    const CURRENT_TIME: f32 = 100.0;
+
+    struct PlantedC4 {
+        activated: bool,
+        defused: bool,
+        bomb_site: i32,
+        time_blow: f32,
+        timer_length: f32,
+        defuse_count_down: Option<f32>,
+    }
+
+    impl PlantedC4 {
+        fn active(time_detonation: f32) -> Self {
+            Self {
+                activated: true,
+                defused: false,
+                bomb_site: 1,
+                time_blow: CURRENT_TIME + time_detonation,
+                timer_length: 40.0,
+                defuse_count_down: None,
+            }
+        }
+
+        fn into_schema(self) -> C_PlantedC4 {
+            let mut buffer = vec![0u8; BUFFER_SIZE];
+            buffer[OFFSET_C4_ACTIVATED] = self.activated as u8;
+            buffer[OFFSET_BOMB_DEFUSED] = self.defused as u8;
+            buffer[OFFSET_BOMB_SITE..OFFSET_BOMB_SITE + 4]
+                .copy_from_slice(&self.bomb_site.to_le_bytes());
+            buffer[OFFSET_C4_BLOW..OFFSET_C4_BLOW + 4]
+                .copy_from_slice(&self.time_blow.to_le_bytes());
+            buffer[OFFSET_TIMER_LENGTH..OFFSET_TIMER_LENGTH + 4]
+                .copy_from_slice(&self.timer_length.to_le_bytes());
+            if let Some(count_down) = self.defuse_count_down {
+                buffer[OFFSET_BEING_DEFUSED] = 1;
+                buffer[OFFSET_DEFUSE_COUNT_DOWN..OFFSET_DEFUSE_COUNT_DOWN + 4]
+                    .copy_from_slice(&count_down.to_le_bytes());
+            }
+
+            VecMemoryDriver::new(BASE_ADDRESS, buffer)
+                .into_handle()
+                .reference_schema::<C_PlantedC4>(0)
+                .unwrap()
+        }
+    }
+
+    fn read_planted_state(bomb: PlantedC4) -> Option<C4Info> {
+        BombInfo::read_planted_state(&bomb.into_schema(), CURRENT_TIME, |_| {
+            Ok(Some("defuser".to_string()))
+        })
+        .unwrap()
+    }
+
+    fn active_state(time_detonation: f32, defuse_time_remaining: Option<f32>) -> C4Info {
+        C4Info {
+            bomb_site: Some(0),
+            state: C4State::Active {
+                time_detonation,
+                timer_length: 40.0,
+                defuse: defuse_time_remaining.map(|time_remaining| BombDefuser {
+                    time_remaining,
+                    player_name: "defuser".to_string(),
+                }),
+                requires_kit: time_detonation < 10.0,
+            },
+            local_defuse_kit: None,
+        }
+    }
+
+    fn final_state(state: C4State) -> C4Info {
+        C4Info {
+            bomb_site: Some(0),
+            state,
+            local_defuse_kit: None,
+        }
+    }
+
+    #[test]
+    fn planted_not_activated() {
+        let mut bomb = PlantedC4::active(30.0);
+        bomb.activated = false;
+
+        assert!(read_planted_state(bomb).is_none());
+    }
+
+    #[test]
+    fn planted_defused() {
+        let mut bomb = PlantedC4::active(12.0);
+        bomb.defused = true;
+
+        let info = read_planted_state(bomb).unwrap();
+        assert_eq!(info.bomb_site, Some(1));
+        assert!(matches!(info.state, C4State::Defused));
+    }
+
+    #[test]
+    fn planted_detonated() {
+        let info = read_planted_state(PlantedC4::active(0.0)).unwrap();
+        assert!(matches!(info.state, C4State::Detonated));
+
+        let info = read_planted_state(PlantedC4::active(-2.0)).unwrap();
+        assert!(matches!(info.state, C4State::Detonated));
+    }
+
+    #[test]
+    fn planted_active() {
+        let mut info = read_planted_state(PlantedC4::active(30.0)).unwrap();
+        match &info.state {
+            C4State::Active {
+                time_detonation,
+                timer_length,
+                defuse,
+                requires_kit,
+            } => {
+                assert_eq!(*time_detonation, 30.0);
+                assert_eq!(*timer_length, 40.0);
+                assert!(defuse.is_none());
+                assert!(!requires_kit);
+            }
+            state => panic!("unexpected bomb state {:?}", state),
+        }
+        assert_eq!(info.time_remaining_fraction(), Some(0.75));
+
+        /* the local player is not an alive counter-terrorist */
+        assert!(!info.local_can_defuse());
+
+        info.local_defuse_kit = Some(false);
+        assert!(info.local_can_defuse());
+    }
+
+    #[test]
+    fn planted_active_invalid_timer_length() {
+        let mut bomb = PlantedC4::active(30.0);
+        bomb.timer_length = 0.0;
+
+        let info = read_planted_state(bomb).unwrap();
+        assert!(matches!(
+            info.state,
+            C4State::Active { timer_length, .. } if timer_length == super::DEFAULT_C4_TIMER
+        ));
+    }
+
+    #[test]
+    fn planted_active_requires_kit() {
+        let mut info = read_planted_state(PlantedC4::active(8.0)).unwrap();
+        assert!(matches!(
+            info.state,
+            C4State::Active {
+                requires_kit: true,
+                ..
+            }
+        ));
+
+        info.local_defuse_kit = Some(false);
+        assert!(!info.local_can_defuse());
+
+        info.local_defuse_kit = Some(true);
+        assert!(info.local_can_defuse());
+    }
+
+    #[test]
+    fn planted_defusing_with_kit() {
+        let mut bomb = PlantedC4::active(8.0);
+        bomb.defuse_count_down = Some(CURRENT_TIME + 5.0);
+
+        let info = read_planted_state(bomb).unwrap();
+        match &info.state {
+            C4State::Active {
+                time_detonation,
+                defuse: Some(defuse),
+                ..
+            } => {
+                assert_eq!(defuse.player_name, "defuser");
+                assert_eq!(defuse.time_remaining, 5.0);
+                assert!(defuse.time_remaining < *time_detonation);
+            }
+            state => panic!("unexpected bomb state {:?}", state),
+        }
+    }
+
+    #[test]
+    fn planted_defusing_without_kit() {
+        let mut bomb = PlantedC4::active(8.0);
+        bomb.defuse_count_down = Some(CURRENT_TIME + 10.0);
+
+        let info = read_planted_state(bomb).unwrap();
+        match &info.state {
+            C4State::Active {
+                time_detonation,
+                defuse: Some(defuse),
+                ..
+            } => {
+                assert_eq!(defuse.time_remaining, 10.0);
+                assert!(defuse.time_remaining > *time_detonation);
+            }
+            state => panic!("unexpected bomb state {:?}", state),
+        }
+    }
+
+    #[test]
+    fn planted_defusing_implausible_defuser() {
+        let mut bomb = PlantedC4::active(20.0);
+        bomb.defuse_count_down = Some(CURRENT_TIME + 5.0);
+
+        let info = BombInfo::read_planted_state(&bomb.into_schema(), CURRENT_TIME, |_| Ok(None))
+            .unwrap()
+            .unwrap();
+        assert!(matches!(info.state, C4State::Active { defuse: None, .. }));
+    }
+
+    #[test]
+    fn bomb_result_defused() {
+        let mut bomb_info = BombInfo::new();
+        for state in [
+            active_state(12.0, None),
+            active_state(8.5, Some(4.0)),
+            final_state(C4State::Defused),
+        ] {
+            bomb_info.bomb_state = Some(state);
+            bomb_info.update_bomb_result();
+        }
+
+        let result = bomb_info.take_bomb_result().unwrap();
+        assert_eq!(result.outcome, BombOutcome::Defused);
+        assert_eq!(result.margin_seconds, 8.5);
+
+        /* the result is only reported once per round */
+        bomb_info.update_bomb_result();
+        assert!(bomb_info.take_bomb_result().is_none());
+    }
+
+    #[test]
+    fn bomb_result_detonated() {
+        let mut bomb_info = BombInfo::new();
+        for state in [
+            active_state(3.0, Some(4.5)),
+            final_state(C4State::Detonated),
+        ] {
+            bomb_info.bomb_state = Some(state);
+            bomb_info.update_bomb_result();
+        }
+
+        let result = bomb_info.take_bomb_result().unwrap();
+        assert_eq!(result.outcome, BombOutcome::Detonated);
+        assert_eq!(result.margin_seconds, 1.5);
+    }
+
+    #[test]
+    fn bomb_result_detonated_without_defuser() {
+        let mut bomb_info = BombInfo::new();
+        for state in [active_state(1.0, None), final_state(C4State::Detonated)] {
+            bomb_info.bomb_state = Some(state);
+            bomb_info.update_bomb_result();
+        }
+
+        let result = bomb_info.take_bomb_result().unwrap();
+        assert_eq!(result.outcome, BombOutcome::Detonated);
+        assert_eq!(result.margin_seconds, 0.0);
+    }
+
+    #[test]
+    fn bomb_result_new_round() {
+        let mut bomb_info = BombInfo::new();
+        bomb_info.bomb_state = Some(final_state(C4State::Defused));
+        bomb_info.update_bomb_result();
+        assert!(bomb_info.take_bomb_result().is_some());
+
+        /* the planted bomb has been removed */
+        bomb_info.bomb_state = None;
+        bomb_info.update_bomb_result();
+        assert!(bomb_info.take_bomb_result().is_none());
+
+        for state in [active_state(2.0, None), final_state(C4State::Detonated)] {
+            bomb_info.bomb_state = Some(state);
+            bomb_info.update_bomb_result();
+        }
+        let result = bomb_info.take_bomb_result().unwrap();
+        assert_eq!(result.outcome, BombOutcome::Detonated);
+    }
+
+    #[test]
+    fn defuse_tracker_transitions() {
+        let mut tracker = DefuseTracker::new();
+        assert!(tracker.update(Some(&active_state(30.0, None))).is_empty());
+
+        assert_eq!(
+            tracker.update(Some(&active_state(29.0, Some(10.0)))),
+            vec![DefuseEvent::Started {
+                player_name: "defuser".to_string()
+            }]
+        );
+
+        /* still defusing */
+        assert!(tracker
+            .update(Some(&active_state(28.0, Some(9.0))))
+            .is_empty());
+
+        assert_eq!(
+            tracker.update(Some(&active_state(27.0, None))),
+            vec![DefuseEvent::Cancelled {
+                player_name: "defuser".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn defuse_tracker_defuser_changed() {
+        let mut tracker = DefuseTracker::new();
+        tracker.update(Some(&active_state(30.0, Some(10.0))));
+
+        let mut state = active_state(29.0, Some(5.0));
+        if let C4State::Active {
+            defuse: Some(defuse),
+            ..
+        } = &mut state.state
+        {
+            defuse.player_name = "other".to_string();
+        }
+
+        assert_eq!(
+            tracker.update(Some(&state)),
+            vec![
+                DefuseEvent::Cancelled {
+                    player_name: "defuser".to_string()
+                },
+                DefuseEvent::Started {
+                    player_name: "other".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn defuse_tracker_bomb_defused() {
+        let mut tracker = DefuseTracker::new();
+        tracker.update(Some(&active_state(30.0, Some(5.0))));
+
+        /* a finished defuse is not reported as cancelled */
+        assert!(tracker
+            .update(Some(&final_state(C4State::Defused)))
+            .is_empty());
+        assert!(tracker.update(None).is_empty());
+    }
+}
@@ -1,52 +1,87 @@
-use std::{
-    ffi::CStr,
-    sync::Arc,
-};
+use std::sync::Arc;
 
 use anyhow::{
     Context,
     Result,
 };
 use cs2::{
+    read_flash_time_remaining,
+    read_pawn_origin,
     BoneFlags,
     CEntityIdentityEx,
     CS2Model,
+    CSPlayerPawnEx,
 };
 use cs2_schema_declaration::{
     define_schema,
     Ptr,
 };
-use cs2_schema_generated::cs2::client::{
-    CCSPlayer_ItemServices,
-    CModelState,
-    CSkeletonInstance,
-    C_CSPlayerPawn,
+use cs2_schema_generated::{
+    cs2::client::{
+        CCSPlayerController,
+        CCSPlayer_ItemServices,
+        CModelState,
+        CSkeletonInstance,
+        C_CSPlayerPawn,
+        C_CSWeaponBaseGun,
+    },
+    EntityHandle,
 };
 use obfstr::obfstr;
 
 use super::Enhancement;
 use crate::{
+    game_rules::is_freeze_period,
+    scope::should_suppress_overlay,
+    session::parse_steam_id,
     settings::{
         AppSettings,
         EspBoxType,
         LineStartPosition,
     },
+    utils::decode_fixed_cstr,
     view::ViewController,
-    weapon::WeaponId,
+    weapon::{
+        read_weapon_skin,
+        read_weapon_stat_trak,
+        SkinInfo,
+        WeaponId,
+        WeaponState,
+    },
 };
 
 pub struct PlayerInfo {
     pub controller_entity_id: u32,
     pub team_id: u8,
 
+    /// Steam id of the controlling player (None for bots)
+    pub steam_id: Option<u64>,
+
+    /// Changes every time the player (re)spawns.
+    /// Use this to distinguish a respawn from a heal.
+    pub spawn_serial: i32,
+
     pub player_health: i32,
+    pub player_armor: i32,
     pub player_has_defuser: bool,
+    pub player_on_ground: bool,
+    pub player_flash_time_remaining: f32,
     pub player_name: String,
+    pub player_place_name: Option<String>,
     pub weapon: WeaponId,
+    pub weapon_state: WeaponState,
+    pub weapon_skin: Option<SkinInfo>,
+    pub weapon_stat_trak: Option<i32>,
 
     pub position: nalgebra::Vector3<f32>,
     pub model: Arc<CS2Model>,
     pub bone_states: Vec<BoneStateData>,
+
+    /// Distance (in units) to the pawn we currently control or observe
+    pub distance: Option<f32>,
+
+    /// The player has been spotted by the local player
+    pub is_visible: bool,
 }
 
 impl PlayerInfo {
@@ -58,6 +93,19 @@ impl PlayerInfo {
     }
 }
 
+/// A target point (hitbox bone) of an enemy player.
+#[derive(Debug, Clone)]
+pub struct AimTarget {
+    pub controller_entity_id: u32,
+    pub bone_index: usize,
+
+    pub position: nalgebra::Vector3<f32>,
+    pub screen_position: mint::Vector2<f32>,
+
+    /// Distance in pixels between the target point and the screen center
+    pub screen_distance: f32,
+}
+
 pub struct BoneStateData {
     pub position: nalgebra::Vector3<f32>,
 }
@@ -97,9 +145,402 @@ impl CModelStateEx for CModelState {
     }
 }
 
+/// Read the name of the map location (callout) the player is currently at.
+/// Returns None if the location is unknown.
+pub fn read_place_name(pawn: &C_CSPlayerPawn) -> anyhow::Result<Option<String>> {
+    let place_name = decode_fixed_cstr(&pawn.m_szLastPlaceName()?).context("place name")?;
+    if place_name.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(place_name))
+    }
+}
+
+/// Read the attachment state of the weapon.
+/// Fields which do not apply for the weapon type keep their default state.
+fn read_weapon_state(
+    weapon: &C_CSWeaponBaseGun,
+    weapon_id: &WeaponId,
+) -> anyhow::Result<WeaponState> {
+    let mut state = WeaponState::default();
+    if weapon_id.has_silencer() {
+        state.silencer_on = weapon.m_bSilencerOn()?;
+    }
+
+    if weapon_id.has_burst_mode() {
+        state.burst_mode = weapon.m_bBurstMode()?;
+    }
+
+    if weapon_id.has_zoom() {
+        state.zoom_level = weapon.m_zoomLevel()?;
+    }
+
+    Ok(state)
+}
+
+/// Information about the local view required to generate relative player information
+pub struct LocalViewInfo {
+    /// Entity index of the pawn we currently control or observe
+    pub observed_entity_index: u32,
+
+    /// Position of the pawn we currently control or observe
+    pub view_position: Option<nalgebra::Vector3<f32>>,
+
+    /// Bit index of the local player within the entity spotted masks
+    pub spotted_mask_index: Option<u32>,
+}
+
+/// Read the pawn currently controlled by the local player.
+/// Returns None if we're currently not connected or dead.
+pub fn read_local_pawn(ctx: &crate::UpdateContext) -> anyhow::Result<Option<C_CSPlayerPawn>> {
+    let local_player_controller = match ctx
+        .cs2_entities
+        .get_local_player_controller()?
+        .try_reference_schema()?
+    {
+        Some(controller) => controller,
+        None => return Ok(None),
+    };
+
+    if !local_player_controller.m_bPawnIsAlive()? {
+        return Ok(None);
+    }
+
+    match ctx
+        .cs2_entities
+        .get_by_handle(&local_player_controller.m_hPawn()?)?
+    {
+        Some(identity) => Ok(Some(
+            identity
+                .entity_ptr::<C_CSPlayerPawn>()?
+                .reference_schema()?,
+        )),
+        None => Ok(None),
+    }
+}
+
+/// Read the remaining flash time (in seconds) of the local player.
+/// Returns None if we're currently not connected or dead.
+pub fn read_local_flash_time_remaining(ctx: &crate::UpdateContext) -> anyhow::Result<Option<f32>> {
+    match read_local_pawn(ctx)? {
+        Some(local_pawn) => Ok(Some(read_flash_time_remaining(&local_pawn, &ctx.globals)?)),
+        None => Ok(None),
+    }
+}
+
+/// Max value of m_flFlashOverlayAlpha (full white screen)
+const FLASH_OVERLAY_ALPHA_MAX: f32 = 255.0;
+
+/// Read how much the local player is currently blinded by a flash.
+/// Returns a value between 0 (not flashed) and 1 (completely blinded).
+/// Returns 0 if we're currently not connected or dead.
+pub fn local_flash_alpha(ctx: &crate::UpdateContext) -> anyhow::Result<f32> {
+    let local_pawn = match read_local_pawn(ctx)? {
+        Some(pawn) => pawn,
+        None => return Ok(0.0),
+    };
+
+    let alpha = local_pawn.m_flFlashOverlayAlpha()?;
+    if !alpha.is_finite() {
+        return Ok(0.0);
+    }
+
+    Ok((alpha / FLASH_OVERLAY_ALPHA_MAX).clamp(0.0, 1.0))
+}
+
+/// Map zones the local player is currently standing in
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ZoneState {
+    pub in_buy_zone: bool,
+    pub in_bomb_zone: bool,
+    pub in_rescue_zone: bool,
+}
+
+/// Read the map zones the local player is currently standing in.
+/// All zones will be false if we're currently not connected or dead.
+pub fn local_zone_state(ctx: &crate::UpdateContext) -> anyhow::Result<ZoneState> {
+    let local_pawn = match read_local_pawn(ctx)? {
+        Some(pawn) => pawn,
+        None => return Ok(ZoneState::default()),
+    };
+
+    Ok(ZoneState {
+        in_buy_zone: local_pawn.m_bInBuyZone()?,
+        in_bomb_zone: local_pawn.m_bInBombZone()?,
+        in_rescue_zone: local_pawn.m_bInHostageRescueZone()?,
+    })
+}
+
+/// Read the pawn the local player is currently spectating.
+/// Returns None if the local player is alive (or not connected).
+pub fn read_observer_target(ctx: &crate::UpdateContext) -> anyhow::Result<Option<C_CSPlayerPawn>> {
+    let local_player_controller = match ctx
+        .cs2_entities
+        .get_local_player_controller()?
+        .try_reference_schema()?
+    {
+        Some(controller) => controller,
+        None => return Ok(None),
+    };
+
+    if local_player_controller.m_bPawnIsAlive()? {
+        return Ok(None);
+    }
+
+    let observer_pawn = match ctx
+        .cs2_entities
+        .get_by_handle(&local_player_controller.m_hObserverPawn()?)?
+    {
+        Some(identity) => identity.entity()?.reference_schema()?,
+        None => return Ok(None),
+    };
+
+    let observer_services = match observer_pawn.m_pObserverServices()?.try_read_schema()? {
+        Some(observer_services) => observer_services,
+        None => return Ok(None),
+    };
+
+    match ctx
+        .cs2_entities
+        .get_by_handle(&observer_services.m_hObserverTarget()?)?
+    {
+        Some(identity) => Ok(identity
+            .entity()?
+            .cast::<C_CSPlayerPawn>()
+            .try_reference_schema()?),
+        None => Ok(None),
+    }
+}
+
+/// Read the name of the player the local player is currently spectating.
+/// Returns None if the local player is alive (or not connected).
+pub fn observed_player_name(ctx: &crate::UpdateContext) -> anyhow::Result<Option<String>> {
+    let observed_pawn = match read_observer_target(ctx)? {
+        Some(pawn) => pawn,
+        None => return Ok(None),
+    };
+
+    ctx.player_name_cache
+        .lookup(ctx.cs2_entities, &observed_pawn.m_hController()?)
+}
+
+/// Player which killed the local player
+pub struct LastKiller {
+    pub player_name: String,
+
+    /// Weapon currently held by the killer.
+    /// This might differ from the weapon used for the kill if the killer switched weapons since.
+    pub weapon: Option<WeaponId>,
+}
+
+/// Read the player who killed the local player.
+/// Returns None if the local player is alive (or not connected) or has not been killed by another player.
+pub fn read_last_killer(ctx: &crate::UpdateContext) -> anyhow::Result<Option<LastKiller>> {
+    let local_player_controller = match ctx
+        .cs2_entities
+        .get_local_player_controller()?
+        .try_reference_schema()?
+    {
+        Some(controller) => controller,
+        None => return Ok(None),
+    };
+
+    if local_player_controller.m_bPawnIsAlive()? {
+        return Ok(None);
+    }
+
+    let local_pawn = match ctx
+        .cs2_entities
+        .get_by_handle(&local_player_controller.m_hPlayerPawn()?)?
+    {
+        Some(identity) => identity.entity()?.reference_schema()?,
+        None => return Ok(None),
+    };
+
+    let killer_index = local_pawn.m_nLastKillerIndex()?;
+    if killer_index == 0 {
+        /* killed by the world or not killed at all */
+        return Ok(None);
+    }
+
+    let killer_identity = match ctx
+        .cs2_entities
+        .get_by_handle(&EntityHandle::<C_CSPlayerPawn>::from_index(killer_index))?
+    {
+        Some(identity) => identity,
+        None => return Ok(None),
+    };
+
+    let class_name = ctx
+        .class_name_cache
+        .lookup(&killer_identity.entity_class_info()?)?;
+    if !class_name
+        .map(|name| name == "C_CSPlayerPawn")
+        .unwrap_or(false)
+    {
+        /* killed by a non player entity */
+        return Ok(None);
+    }
+
+    let killer_pawn = killer_identity.entity()?.reference_schema()?;
+    let player_name = match ctx
+        .player_name_cache
+        .lookup(ctx.cs2_entities, &killer_pawn.m_hController()?)?
+    {
+        Some(name) => name,
+        None => return Ok(None),
+    };
+
+    let weapon = match killer_pawn.m_pClippingWeapon()?.try_reference_schema()? {
+        Some(weapon) => {
+            let weapon_id = weapon
+                .m_AttributeManager()?
+                .m_Item()?
+                .m_iItemDefinitionIndex()?;
+            WeaponId::from_id(weapon_id)
+        }
+        None => None,
+    };
+
+    Ok(Some(LastKiller {
+        player_name,
+        weapon,
+    }))
+}
+
+/// Read the local view.
+/// Returns None if we're currently not connected.
+pub fn read_local_view(ctx: &crate::UpdateContext) -> anyhow::Result<Option<LocalViewInfo>> {
+    let local_player_controller = ctx
+        .cs2_entities
+        .get_local_player_controller()?
+        .try_reference_schema()
+        .with_context(|| obfstr!("failed to read local player controller").to_string())?;
+
+    let local_player_controller = match local_player_controller {
+        Some(controller) => controller,
+        None => {
+            /* We're currently not connected */
+            return Ok(None);
+        }
+    };
+
+    let observed_entity_handle = if local_player_controller.m_bPawnIsAlive()? {
+        local_player_controller.m_hPawn()?.get_entity_index()
+    } else {
+        let local_obs_pawn = match {
+            ctx.cs2_entities
+                .get_by_handle(&local_player_controller.m_hObserverPawn()?)?
+        } {
+            Some(pawn) => pawn.entity()?.reference_schema()?,
+            None => {
+                /* this is odd... */
+                return Ok(None);
+            }
+        };
+
+        local_obs_pawn
+            .m_pObserverServices()?
+            .read_schema()?
+            .m_hObserverTarget()?
+            .get_entity_index()
+    };
+
+    let observed_pawn_handle = EntityHandle::<C_CSPlayerPawn>::from_index(observed_entity_handle);
+    let observed_pawn = ctx.cs2_entities.get_by_handle(&observed_pawn_handle)?;
+    let view_position = match observed_pawn {
+        Some(identity) => {
+            let observed_pawn = identity.entity()?.reference_schema()?;
+            read_pawn_origin(&observed_pawn)?
+                .map(|origin| nalgebra::Vector3::<f32>::from_column_slice(&origin))
+        }
+        None => None,
+    };
+
+    let local_controller_index = local_player_controller
+        .m_pEntity()?
+        .read_schema()?
+        .handle::<()>()?
+        .get_entity_index();
+
+    Ok(Some(LocalViewInfo {
+        observed_entity_index: observed_entity_handle,
+        view_position,
+        spotted_mask_index: local_controller_index.checked_sub(1),
+    }))
+}
+
+/// Read all living players except the pawn we currently control or observe.
+pub fn read_all_players(ctx: &crate::UpdateContext) -> anyhow::Result<Vec<PlayerInfo>> {
+    let local_view = match read_local_view(ctx)? {
+        Some(local_view) => local_view,
+        None => return Ok(Vec::new()),
+    };
+
+    let mut players = Vec::with_capacity(16);
+    for entity_identity in ctx.cs2_entities.all_identities() {
+        if entity_identity.handle::<()>()?.get_entity_index() == local_view.observed_entity_index {
+            /* current pawn we control/observe */
+            continue;
+        }
+
+        let entity_class = ctx
+            .class_name_cache
+            .lookup(&entity_identity.entity_class_info()?)?;
+        if !entity_class
+            .map(|name| *name == "C_CSPlayerPawn")
+            .unwrap_or(false)
+        {
+            /* entity is not a player pawn */
+            continue;
+        }
+
+        let player_pawn = entity_identity.entity_ptr::<C_CSPlayerPawn>()?;
+        match PlayerESP::generate_player_info(ctx, &local_view, &player_pawn) {
+            Ok(Some(info)) => players.push(info),
+            Ok(None) => {}
+            Err(error) => {
+                log::warn!(
+                    "Failed to generate player pawn ESP info for {:X}: {:#}",
+                    player_pawn.address()?,
+                    error
+                );
+            }
+        }
+    }
+
+    Ok(players)
+}
+
+/// Read all players sorted by descending distance to the origin (furthest first).
+/// This is the order in which the players should be rendered.
+///
+/// If a limit is given, only the nearest `limit` players will be returned.
+pub fn read_all_players_sorted(
+    ctx: &crate::UpdateContext,
+    origin: &nalgebra::Vector3<f32>,
+    limit: Option<usize>,
+) -> anyhow::Result<Vec<PlayerInfo>> {
+    let mut players = read_all_players(ctx)?
+        .into_iter()
+        .map(|player| ((player.position - origin).norm(), player))
+        .collect::<Vec<_>>();
+
+    players.sort_by(|(distance_a, _), (distance_b, _)| distance_b.total_cmp(distance_a));
+    if let Some(limit) = limit {
+        let skip = players.len().saturating_sub(limit);
+        players.drain(0..skip);
+    }
+
+    Ok(players.into_iter().map(|(_, player)| player).collect())
+}
+
 pub struct PlayerESP {
     players: Vec<PlayerInfo>,
     local_team_id: u8,
+
+    /// Aim targets will not be selected during the freeze period
+    freeze_period: bool,
 }
 
 impl PlayerESP {
@@ -107,12 +548,14 @@ impl PlayerESP {
         PlayerESP {
             players: Default::default(),
             local_team_id: 0,
+
+            freeze_period: false,
         }
     }
 
-    fn generate_player_info(
-        &self,
+    pub fn generate_player_info(
         ctx: &crate::UpdateContext,
+        local_view: &LocalViewInfo,
         player_pawn: &Ptr<C_CSPlayerPawn>,
     ) -> anyhow::Result<Option<PlayerInfo>> {
         let player_pawn = player_pawn
@@ -125,25 +568,27 @@ impl PlayerESP {
         }
 
         /* Will be an instance of CSkeletonInstance */
-        let game_screen_node = player_pawn
+        let game_screen_node = match player_pawn
             .m_pGameSceneNode()?
             .cast::<CSkeletonInstance>()
-            .read_schema()?;
+            .try_read_schema()?
+        {
+            Some(node) => node,
+            /* pawn is still being created */
+            None => return Ok(None),
+        };
         if game_screen_node.m_bDormant()? {
             return Ok(None);
         }
 
         let controller_handle = player_pawn.m_hController()?;
-        let current_controller = ctx.cs2_entities.get_by_handle(&controller_handle)?;
+        let current_name = ctx
+            .player_name_cache
+            .lookup(ctx.cs2_entities, &controller_handle)?;
 
         let player_team = player_pawn.m_iTeamNum()?;
-        let player_name = if let Some(identity) = &current_controller {
-            let player_controller = identity.entity()?.reference_schema()?;
-            CStr::from_bytes_until_nul(&player_controller.m_iszPlayerName()?)
-                .context("player name missing nul terminator")?
-                .to_str()
-                .context("invalid player name")?
-                .to_string()
+        let player_name = if let Some(name) = current_name {
+            name
         } else {
             /*
              * This is the case for pawns which are not controllel by a player controller.
@@ -159,14 +604,56 @@ impl PlayerESP {
             return Ok(None);
         };
 
+        let controller = match ctx.cs2_entities.get_by_handle(&controller_handle)? {
+            Some(identity) => Some(
+                identity
+                    .entity_ptr::<CCSPlayerController>()?
+                    .reference_schema()?,
+            ),
+            None => None,
+        };
+
+        let steam_id = match &controller {
+            Some(controller) => parse_steam_id(controller.m_steamID()?),
+            None => None,
+        };
+
+        /*
+         * The pawn entity might be reused when respawning.
+         * The pawn lifetime start tick of the controller changes on every spawn.
+         */
+        let spawn_serial = match &controller {
+            Some(controller) => controller.m_iPawnLifetimeStart()?,
+            None => player_pawn.m_nCreationTick()?,
+        };
+
         let player_has_defuser = player_pawn
             .m_pItemServices()?
             .cast::<CCSPlayer_ItemServices>()
             .reference_schema()?
             .m_bHasDefuser()?;
+        let player_on_ground = player_pawn.is_on_ground()?;
+        let player_place_name = read_place_name(&player_pawn)?;
 
-        let position =
-            nalgebra::Vector3::<f32>::from_column_slice(&game_screen_node.m_vecAbsOrigin()?);
+        let position = match read_pawn_origin(&player_pawn)? {
+            Some(position) => nalgebra::Vector3::<f32>::from_column_slice(&position),
+            /* pawn has not been positioned yet */
+            None => return Ok(None),
+        };
+        let distance = local_view
+            .view_position
+            .map(|view_position| (position - view_position).norm());
+
+        let is_visible = match local_view.spotted_mask_index {
+            Some(index) => {
+                let spotted_mask = player_pawn.m_entitySpottedState()?.m_bSpottedByMask()?;
+                spotted_mask
+                    .get(index as usize / 32)
+                    .map(|mask| (mask & (1 << (index % 32))) != 0)
+                    .unwrap_or(false)
+            }
+            None => false,
+        };
 
         let model = game_screen_node
             .m_modelState()?
@@ -183,31 +670,104 @@ impl PlayerESP {
             .map(|bone| bone.try_into())
             .collect::<Result<Vec<_>>>()?;
 
-        let weapon = player_pawn.m_pClippingWeapon()?.try_read_schema()?;
-        let weapon_type = if let Some(weapon) = weapon {
-            weapon
+        let weapon = player_pawn
+            .m_pClippingWeapon()?
+            .cast::<C_CSWeaponBaseGun>()
+            .try_read_schema()?;
+        let (weapon, weapon_state, weapon_skin, weapon_stat_trak) = if let Some(weapon) = weapon {
+            let weapon_type = weapon
                 .m_AttributeManager()?
                 .m_Item()?
-                .m_iItemDefinitionIndex()?
+                .m_iItemDefinitionIndex()?;
+            let weapon_type = WeaponId::from_id(weapon_type).unwrap_or(WeaponId::Unknown);
+
+            let weapon_state = read_weapon_state(&weapon, &weapon_type)?;
+            let weapon_skin = read_weapon_skin(&weapon)?;
+            let weapon_stat_trak = read_weapon_stat_trak(&weapon)?;
+            (weapon_type, weapon_state, weapon_skin, weapon_stat_trak)
         } else {
-            WeaponId::Knife.id()
+            (WeaponId::Knife, WeaponState::default(), None, None)
         };
 
         Ok(Some(PlayerInfo {
             controller_entity_id: controller_handle.get_entity_index(),
             team_id: player_team,
+            steam_id,
+            spawn_serial,
 
             player_name,
             player_has_defuser,
+            player_on_ground,
+            player_flash_time_remaining: read_flash_time_remaining(&player_pawn, &ctx.globals)?,
+            player_place_name,
             player_health,
-            weapon: WeaponId::from_id(weapon_type).unwrap_or(WeaponId::Unknown),
+            player_armor: player_pawn.m_ArmorValue()?,
+            weapon,
+            weapon_state,
+            weapon_skin,
+            weapon_stat_trak,
 
             position,
             bone_states,
             model: model.clone(),
+
+            distance,
+            is_visible,
         }))
     }
 
+    /// Select the enemy target point which is closest (in pixels) to the crosshair.
+    /// In contrast to an angular FOV cone this only considers the screen space.
+    /// Target points which are off screen will be ignored.
+    pub fn select_target_screen(&self, view: &ViewController) -> Option<AimTarget> {
+        if self.freeze_period {
+            return None;
+        }
+
+        let screen_center =
+            nalgebra::Vector2::new(view.screen_bounds.x / 2.0, view.screen_bounds.y / 2.0);
+
+        let mut result: Option<AimTarget> = None;
+        for entry in self.players.iter() {
+            if entry.team_id == self.local_team_id {
+                continue;
+            }
+
+            let bones = entry.model.bones.iter().zip(entry.bone_states.iter());
+            for (bone_index, (bone, state)) in bones.enumerate() {
+                if (bone.flags & BoneFlags::FlagHitbox as u32) == 0 {
+                    continue;
+                }
+
+                let screen_position = match view.world_to_screen(&state.position, false) {
+                    Some(position) => position,
+                    None => continue,
+                };
+
+                let screen_distance =
+                    (nalgebra::Vector2::new(screen_position.x, screen_position.y) - screen_center)
+                        .norm();
+                if let Some(current) = &result {
+                    if current.screen_distance <= screen_distance {
+                        continue;
+                    }
+                }
+
+                result = Some(AimTarget {
+                    controller_entity_id: entry.controller_entity_id,
+                    bone_index,
+
+                    position: state.position,
+                    screen_position,
+
+                    screen_distance,
+                });
+            }
+        }
+
+        result
+    }
+
     pub fn calculate_rainbow_color(value: f32, alpha: f32) -> [f32; 4] {
         let sin_value =
             |offset: f32| (2.0 * std::f32::consts::PI * value * 0.75 + offset).sin() * 0.5 + 1.0;
@@ -269,7 +829,7 @@ impl Enhancement for PlayerESP {
             return Ok(());
         }
 
-        self.players.reserve(16);
+        self.freeze_period = is_freeze_period(ctx)?;
 
         let local_player_controller = ctx
             .cs2_entities
@@ -285,60 +845,13 @@ impl Enhancement for PlayerESP {
             }
         };
 
-        let observice_entity_handle = if local_player_controller.m_bPawnIsAlive()? {
-            local_player_controller.m_hPawn()?.get_entity_index()
-        } else {
-            let local_obs_pawn = match {
-                ctx.cs2_entities
-                    .get_by_handle(&local_player_controller.m_hObserverPawn()?)?
-            } {
-                Some(pawn) => pawn.entity()?.reference_schema()?,
-                None => {
-                    /* this is odd... */
-                    return Ok(());
-                }
-            };
-
-            local_obs_pawn
-                .m_pObserverServices()?
-                .read_schema()?
-                .m_hObserverTarget()?
-                .get_entity_index()
-        };
-
         self.local_team_id = local_player_controller.m_iPendingTeamNum()?;
-
-        for entity_identity in ctx.cs2_entities.all_identities() {
-            if entity_identity.handle::<()>()?.get_entity_index() == observice_entity_handle {
-                /* current pawn we control/observe */
-                continue;
-            }
-
-            let entity_class = ctx
-                .class_name_cache
-                .lookup(&entity_identity.entity_class_info()?)?;
-            if !entity_class
-                .map(|name| *name == "C_CSPlayerPawn")
-                .unwrap_or(false)
-            {
-                /* entity is not a player pawn */
-                continue;
-            }
-
-            let player_pawn = entity_identity.entity_ptr::<C_CSPlayerPawn>()?;
-            match self.generate_player_info(ctx, &player_pawn) {
-                Ok(Some(info)) => self.players.push(info),
-                Ok(None) => {}
-                Err(error) => {
-                    log::warn!(
-                        "Failed to generate player pawn ESP info for {:X}: {:#}",
-                        player_pawn.address()?,
-                        error
-                    );
-                }
-            }
+        if should_suppress_overlay(ctx) {
+            return Ok(());
         }
 
+        self.players = read_all_players(ctx)?;
+
         Ok(())
     }
 
@@ -489,7 +1002,7 @@ impl Enhancement for PlayerESP {
                     }
 
                     if settings.esp_info_weapon {
-                        let text = entry.weapon.display_name();
+                        let text = entry.weapon.display_name_with_state(&entry.weapon_state);
                         let [text_width, _] = ui.calc_text_size(&text);
 
                         let mut pos = pos.clone();
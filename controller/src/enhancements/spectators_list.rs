@@ -1,5 +1,3 @@
-use std::ffi::CStr;
-
 use anyhow::Context;
 use cs2::CEntityIdentityEx;
 use cs2_schema_generated::cs2::client::{
@@ -179,21 +177,14 @@ impl Enhancement for SpectatorsList {
                 continue;
             }
 
-            let current_player_controller =
-                ctx.cs2_entities.get_by_handle(&player_controller_handle)?;
-
-            let player_controller = if let Some(identity) = &current_player_controller {
-                identity.entity()?.reference_schema()?
-            } else {
-                continue;
+            let spectator_name = match ctx
+                .player_name_cache
+                .lookup(ctx.cs2_entities, &player_controller_handle)?
+            {
+                Some(name) => name,
+                None => continue,
             };
 
-            let spectator_name = CStr::from_bytes_until_nul(&player_controller.m_iszPlayerName()?)
-                .context("player name missing nul terminator")?
-                .to_str()
-                .context("invalid player name")?
-                .to_string();
-
             self.spectators.push(SpectatorInfo { spectator_name });
         }
 
@@ -13,6 +13,7 @@ use valthrun_kernel_interface::MouseState;
 
 use super::Enhancement;
 use crate::{
+    game_rules::is_freeze_period,
     settings::AppSettings,
     view::{
         LocalCrosshair,
@@ -58,6 +59,10 @@ impl TriggerBot {
             return Ok(false);
         }
 
+        if is_freeze_period(ctx)? {
+            return Ok(false);
+        }
+
         if ctx.settings.trigger_bot_team_check {
             let crosshair_entity = ctx
                 .cs2_entities
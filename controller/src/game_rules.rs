@@ -0,0 +1,200 @@
+use std::collections::BTreeMap;
+
+use anyhow::Context;
+use cs2::{
+    CEntityIdentityEx,
+    EntitySystem,
+};
+use cs2_schema_generated::cs2::client::{
+    C_CSGameRules,
+    C_CSGameRulesProxy,
+};
+
+use crate::{
+    class_name_cache::ClassNameCache,
+    player_name_cache::read_player_name,
+    UpdateContext,
+};
+
+/// Read the current game rules.
+/// Returns None if no game rules are available (e.g. not connected).
+///
+/// The game rules will be read once per frame and are available via `UpdateContext::game_rules`.
+pub fn read_game_rules(
+    cs2_entities: &EntitySystem,
+    class_name_cache: &ClassNameCache,
+) -> anyhow::Result<Option<C_CSGameRules>> {
+    for entity_identity in cs2_entities.all_identities() {
+        let class_name = class_name_cache
+            .lookup(&entity_identity.entity_class_info()?)
+            .context("class name")?;
+
+        if !class_name
+            .map(|name| name == "C_CSGameRulesProxy")
+            .unwrap_or(false)
+        {
+            continue;
+        }
+
+        let proxy = entity_identity
+            .entity_ptr::<C_CSGameRulesProxy>()?
+            .reference_schema()
+            .context("game rules proxy")?;
+
+        return proxy.m_pGameRules()?.try_read_schema();
+    }
+
+    Ok(None)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GamePhase {
+    Warmup,
+    Live,
+
+    /// Half time
+    Intermission,
+
+    /// The match has ended
+    GameOver,
+}
+
+/* values of C_CSGameRules::m_gamePhase */
+const GAMEPHASE_HALFTIME: i32 = 4;
+const GAMEPHASE_MATCH_ENDED: i32 = 5;
+
+/// Read the current game phase.
+/// Returns None if no game rules are available.
+pub fn game_phase(ctx: &UpdateContext) -> anyhow::Result<Option<GamePhase>> {
+    let game_rules = match &ctx.game_rules {
+        Some(game_rules) => game_rules,
+        None => return Ok(None),
+    };
+
+    if game_rules.m_bWarmupPeriod()? {
+        return Ok(Some(GamePhase::Warmup));
+    }
+
+    Ok(Some(match game_rules.m_gamePhase()? {
+        GAMEPHASE_HALFTIME => GamePhase::Intermission,
+        GAMEPHASE_MATCH_ENDED => GamePhase::GameOver,
+        _ => GamePhase::Live,
+    }))
+}
+
+/// Returns true during the freeze time at the start of a round.
+/// Returns false if no game rules are available.
+pub fn is_freeze_period(ctx: &UpdateContext) -> anyhow::Result<bool> {
+    match &ctx.game_rules {
+        Some(game_rules) => game_rules.m_bFreezePeriod(),
+        None => Ok(false),
+    }
+}
+
+/// Summary of a finished round
+#[derive(Debug, Clone)]
+pub struct RoundSummary {
+    pub winner_team: u8,
+
+    /// Raw value of C_CSGameRules::m_eRoundWinReason
+    pub reason: i32,
+
+    /// Name of the round MVP.
+    /// None if the MVP has not (yet) been awarded.
+    pub mvp_name: Option<String>,
+}
+
+/// Emits a round summary once the current round is over.
+///
+/// The CS2 client does not expose the round MVP directly.
+/// The MVP is detected by tracking the MVP count of every player controller.
+/// As the MVP might be awarded shortly after the round has ended, the summary
+/// will be held back until the MVP is known or the next round starts.
+pub struct RoundSummaryTracker {
+    round_over: bool,
+    pending_summary: Option<RoundSummary>,
+    mvp_counts: BTreeMap<u32, i32>,
+}
+
+impl RoundSummaryTracker {
+    pub fn new() -> Self {
+        Self {
+            round_over: false,
+            pending_summary: None,
+            mvp_counts: Default::default(),
+        }
+    }
+
+    /// Returns the MVP name if the MVP count of a player increased.
+    fn update_mvp_counts(&mut self, ctx: &UpdateContext) -> anyhow::Result<Option<String>> {
+        let mut mvp_name = None;
+        for controller in ctx.cs2_entities.get_player_controllers()? {
+            let controller = match controller.try_reference_schema()? {
+                Some(controller) => controller,
+                None => continue,
+            };
+
+            let entity_index = controller
+                .m_pEntity()?
+                .read_schema()?
+                .handle::<()>()?
+                .get_entity_index();
+
+            let mvp_count = controller.m_iMVPs()?;
+            let last_count = self.mvp_counts.insert(entity_index, mvp_count);
+            if last_count.map(|count| mvp_count > count).unwrap_or(false) {
+                mvp_name = Some(read_player_name(&controller)?);
+            }
+        }
+
+        Ok(mvp_name)
+    }
+
+    /// Returns the round summary once for every finished round.
+    pub fn update(&mut self, ctx: &UpdateContext) -> anyhow::Result<Option<RoundSummary>> {
+        let game_rules = match &ctx.game_rules {
+            Some(game_rules) => game_rules,
+            None => {
+                self.round_over = false;
+                self.mvp_counts.clear();
+                return Ok(self.pending_summary.take());
+            }
+        };
+
+        let mvp_name = self.update_mvp_counts(ctx)?;
+
+        /* m_iRoundWinStatus contains the winning team or zero while the round is running */
+        let winner_team = game_rules.m_iRoundWinStatus()?;
+        if winner_team <= 0 {
+            /* next round started, MVP has not been awarded */
+            self.round_over = false;
+            return Ok(self.pending_summary.take());
+        }
+
+        if !self.round_over {
+            self.round_over = true;
+            self.pending_summary = Some(RoundSummary {
+                winner_team: winner_team as u8,
+                reason: game_rules.m_eRoundWinReason()?,
+                mvp_name: None,
+            });
+        }
+
+        if mvp_name.is_some() {
+            if let Some(summary) = self.pending_summary.as_mut() {
+                summary.mvp_name = mvp_name;
+            }
+        }
+
+        if self
+            .pending_summary
+            .as_ref()
+            .map(|summary| summary.mvp_name.is_some())
+            .unwrap_or(false)
+        {
+            return Ok(self.pending_summary.take());
+        }
+
+        Ok(None)
+    }
+}
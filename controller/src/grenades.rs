@@ -0,0 +1,218 @@
+use anyhow::Context;
+use cs2::{
+    read_origin,
+    CEntityIdentityEx,
+};
+use cs2_schema_declaration::SchemaValue;
+use cs2_schema_generated::{
+    cs2::client::{
+        C_BaseEntity,
+        C_BaseGrenade,
+        C_BasePlayerPawn,
+        C_Inferno,
+    },
+    EntityHandle,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::UpdateContext;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum GrenadeKind {
+    Smoke,
+    Molotov,
+    Flashbang,
+    HighExplosive,
+    Decoy,
+}
+
+impl GrenadeKind {
+    pub fn from_class_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "C_SmokeGrenadeProjectile" => Self::Smoke,
+            "C_MolotovProjectile" => Self::Molotov,
+            "C_FlashbangProjectile" => Self::Flashbang,
+            /* the client does not have a dedicated class for HE grenades */
+            "C_BaseCSGrenadeProjectile" => Self::HighExplosive,
+            "C_DecoyProjectile" => Self::Decoy,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GrenadeInfo {
+    pub kind: GrenadeKind,
+    pub position: nalgebra::Vector3<f32>,
+
+    /// Team of the player who has thrown the grenade.
+    /// None if the grenade has no (resolvable) owner e.g. world entities.
+    pub owner_team: Option<u8>,
+
+    /// Name of the player who has thrown the grenade.
+    /// None if the grenade has no (resolvable) owning player.
+    pub owner_name: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct InfernoInfo {
+    pub position: nalgebra::Vector3<f32>,
+
+    /// Team of the player who owns the fire.
+    /// None if the fire has no (resolvable) owner.
+    pub owner_team: Option<u8>,
+
+    /// Name of the player who owns the fire.
+    /// None if the fire has no (resolvable) owning player.
+    pub owner_name: Option<String>,
+
+    /// Remaining time in seconds until the fire extinguishes
+    pub remaining_time: f32,
+}
+
+/// Read the team and the player name of the owner.
+fn read_owner<T: SchemaValue>(
+    ctx: &UpdateContext,
+    owner: &EntityHandle<T>,
+) -> anyhow::Result<(Option<u8>, Option<String>)> {
+    if !owner.is_valid() {
+        return Ok((None, None));
+    }
+
+    let identity = match ctx.cs2_entities.get_by_handle(owner)? {
+        Some(identity) => identity,
+        None => return Ok((None, None)),
+    };
+
+    let entity = identity
+        .entity()?
+        .cast::<C_BaseEntity>()
+        .reference_schema()?;
+    let owner_team = Some(entity.m_iTeamNum()?);
+
+    if !ctx
+        .class_name_cache
+        .class_is_a(&identity.entity_class_info()?, "C_BasePlayerPawn")?
+    {
+        /* owner is not a player */
+        return Ok((owner_team, None));
+    }
+
+    let pawn = identity
+        .entity()?
+        .cast::<C_BasePlayerPawn>()
+        .reference_schema()?;
+    let owner_name = ctx
+        .player_name_cache
+        .lookup(ctx.cs2_entities, &pawn.m_hController()?)?;
+
+    Ok((owner_team, owner_name))
+}
+
+/// Read all grenade projectiles currently present in the world.
+pub fn read_grenades(ctx: &UpdateContext) -> anyhow::Result<Vec<GrenadeInfo>> {
+    let mut result = Vec::new();
+
+    for entity_identity in ctx.cs2_entities.all_identities() {
+        let class_info = entity_identity.entity_class_info()?;
+        if !ctx
+            .class_name_cache
+            .class_is_a(&class_info, "C_BaseCSGrenadeProjectile")?
+        {
+            continue;
+        }
+
+        let class_name = ctx
+            .class_name_cache
+            .lookup(&class_info)
+            .context("class name")?;
+
+        let kind = match class_name.and_then(|name| GrenadeKind::from_class_name(name)) {
+            Some(kind) => kind,
+            None => continue,
+        };
+
+        let grenade = entity_identity
+            .entity_ptr::<C_BaseGrenade>()?
+            .read_schema()
+            .context("grenade schema")?;
+
+        let game_scene_node = match grenade.m_pGameSceneNode()?.try_read_schema()? {
+            Some(node) => node,
+            /* entity is still being created */
+            None => continue,
+        };
+        if game_scene_node.m_bDormant()? {
+            continue;
+        }
+
+        let position = match read_origin(&grenade)? {
+            Some(position) => nalgebra::Vector3::<f32>::from_column_slice(&position),
+            None => continue,
+        };
+        let (owner_team, owner_name) = read_owner(ctx, &grenade.m_hThrower()?)?;
+
+        result.push(GrenadeInfo {
+            kind,
+            position,
+            owner_team,
+            owner_name,
+        });
+    }
+
+    Ok(result)
+}
+
+/// Read all burning infernos (molotov/incendiary fires).
+pub fn read_infernos(ctx: &UpdateContext) -> anyhow::Result<Vec<InfernoInfo>> {
+    let mut result = Vec::new();
+
+    let current_time = ctx.globals.cur_time()?;
+    let tick_interval = ctx.globals.tick_interval()?;
+
+    for entity_identity in ctx.cs2_entities.all_identities() {
+        let class_name = ctx
+            .class_name_cache
+            .lookup(&entity_identity.entity_class_info()?)
+            .context("class name")?;
+
+        if !class_name.map(|name| name == "C_Inferno").unwrap_or(false) {
+            continue;
+        }
+
+        let inferno = entity_identity
+            .entity_ptr::<C_Inferno>()?
+            .read_schema()
+            .context("inferno schema")?;
+
+        let game_scene_node = match inferno.m_pGameSceneNode()?.try_read_schema()? {
+            Some(node) => node,
+            /* entity is still being created */
+            None => continue,
+        };
+        if game_scene_node.m_bDormant()? {
+            continue;
+        }
+
+        let time_begin = inferno.m_nFireEffectTickBegin()? as f32 * tick_interval;
+        let remaining_time = (time_begin + inferno.m_nFireLifetime()? - current_time).max(0.0);
+
+        let position = match read_origin(&inferno)? {
+            Some(position) => nalgebra::Vector3::<f32>::from_column_slice(&position),
+            None => continue,
+        };
+        let (owner_team, owner_name) = read_owner(ctx, &inferno.m_hOwnerEntity()?)?;
+
+        result.push(InfernoInfo {
+            position,
+            owner_team,
+            owner_name,
+            remaining_time,
+        });
+    }
+
+    Ok(result)
+}
@@ -37,10 +37,15 @@ use cs2::{
     CS2Handle,
     CS2Model,
     CS2Offsets,
+    ConnectionState,
+    CreateOptions,
     EntitySystem,
     Globals,
 };
+use cs2_schema_generated::cs2::client::C_CSGameRules;
+use diagnostics::PerfInfo;
 use enhancements::Enhancement;
+use game_rules::GamePhase;
 use imgui::{
     Condition,
     FontConfig,
@@ -56,6 +61,7 @@ use overlay::{
     OverlayTarget,
     SystemRuntimeController,
 };
+use player_name_cache::PlayerNameCache;
 use settings::{
     load_app_settings,
     AppSettings,
@@ -87,14 +93,27 @@ use crate::{
         TriggerBot,
     },
     settings::save_app_settings,
+    utils::ErrorBudget,
     view::LocalCrosshair,
     winver::version_info,
 };
 
 mod cache;
 mod class_name_cache;
+mod diagnostics;
 mod enhancements;
+mod game_rules;
+mod grenades;
+mod money;
+mod player_name_cache;
+mod scope;
+mod session;
 mod settings;
+mod snapshot;
+mod spawn_points;
+mod team_status;
+mod team_tracker;
+mod tick_base;
 mod utils;
 mod view;
 mod weapon;
@@ -124,15 +143,27 @@ pub struct UpdateContext<'a> {
     pub input: &'a dyn KeyboardInput,
 
     pub cs2: &'a Arc<CS2Handle>,
+    pub cs2_offsets: &'a CS2Offsets,
     pub cs2_entities: &'a EntitySystem,
 
     pub model_cache: &'a EntryCache<u64, CS2Model>,
     pub class_name_cache: &'a ClassNameCache,
+    pub player_name_cache: &'a PlayerNameCache,
     pub view_controller: &'a ViewController,
 
     pub globals: Globals,
+
+    /// Game rules of the current frame.
+    /// None if no game rules are available (e.g. not connected).
+    pub game_rules: Option<C_CSGameRules>,
 }
 
+/// Consecutive update failures after which an enhancement will be disabled temporarily
+const ENHANCEMENT_MAX_FAILURES: usize = 10;
+
+/// Time after which a disabled enhancement will be updated again
+const ENHANCEMENT_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
 pub struct AppFonts {
     valthrun: FontId,
 }
@@ -148,13 +179,28 @@ pub struct Application {
 
     pub model_cache: EntryCache<u64, CS2Model>,
     pub class_name_cache: ClassNameCache,
+    pub player_name_cache: PlayerNameCache,
     pub view_controller: ViewController,
 
+    /// Address of the last known local player controller.
+    /// Used to detect map changes.
+    pub last_local_controller: u64,
+
     pub enhancements: Vec<Rc<RefCell<dyn Enhancement>>>,
 
+    /// Error budget of every enhancement (same order as `enhancements`)
+    pub enhancement_budgets: Vec<ErrorBudget>,
+
     pub frame_read_calls: usize,
     pub last_total_read_calls: usize,
 
+    pub perf_info: PerfInfo,
+    pub game_phase: Option<GamePhase>,
+    pub connection_state: ConnectionState,
+
+    #[cfg(feature = "read-metrics")]
+    pub frame_read_metrics: cs2::ReadMetricsSnapshot,
+
     pub settings: Rc<RefCell<AppSettings>>,
     pub settings_visible: bool,
     pub settings_dirty: bool,
@@ -173,6 +219,9 @@ impl Application {
     }
 
     pub fn pre_update(&mut self, controller: &mut SystemRuntimeController) -> anyhow::Result<()> {
+        /* Do not read any memory while CS2 is in the background */
+        self.cs2.set_paused(!controller.target_window_focused());
+
         if self.settings_dirty {
             self.settings_dirty = false;
             let mut settings = self.settings.borrow_mut();
@@ -231,6 +280,18 @@ impl Application {
             }
         }
 
+        if self.cs2.is_paused() {
+            /* keep the last known state */
+            return Ok(());
+        }
+
+        self.connection_state = cs2::read_connection_state(&self.cs2, &self.cs2_offsets)
+            .with_context(|| obfstr!("failed to read connection state").to_string())?;
+        if self.connection_state != ConnectionState::Connected {
+            /* entity data is not valid until we're fully connected */
+            return Ok(());
+        }
+
         self.view_controller
             .update_screen_bounds(mint::Vector2::from_slice(&ui.io().display_size));
         self.view_controller.update_view_matrix(&self.cs2)?;
@@ -249,28 +310,56 @@ impl Application {
             .update_cache(self.cs2_entities.all_identities())
             .with_context(|| obfstr!("failed to update class name cache").to_string())?;
 
+        let local_controller = self.cs2_entities.get_local_player_controller()?.address()?;
+        if local_controller != self.last_local_controller {
+            /* A new local controller gets created on map change */
+            self.last_local_controller = local_controller;
+            self.player_name_cache.clear();
+        }
+
+        let game_rules = game_rules::read_game_rules(&self.cs2_entities, &self.class_name_cache)
+            .with_context(|| obfstr!("failed to read game rules").to_string())?;
+
         let update_context = UpdateContext {
             cs2: &self.cs2,
+            cs2_offsets: &self.cs2_offsets,
             cs2_entities: &self.cs2_entities,
 
             settings: &*settings,
             input: ui,
 
             globals,
+            game_rules,
             class_name_cache: &self.class_name_cache,
+            player_name_cache: &self.player_name_cache,
             view_controller: &self.view_controller,
             model_cache: &self.model_cache,
         };
 
-        for enhancement in self.enhancements.iter() {
-            let mut hack = enhancement.borrow_mut();
-            hack.update(&update_context)?;
+        self.game_phase = game_rules::game_phase(&update_context)?;
+        if self.game_phase != Some(GamePhase::GameOver) {
+            let enhancements = self
+                .enhancements
+                .iter()
+                .zip(self.enhancement_budgets.iter_mut());
+            for (enhancement, budget) in enhancements {
+                /* a failing enhancement should not prevent the other enhancements from being updated */
+                let mut hack = enhancement.borrow_mut();
+                budget.execute(|| hack.update(&update_context));
+            }
         }
 
+        self.perf_info = diagnostics::read_performance_info(&update_context);
+
         let read_calls = self.cs2.ke_interface.total_read_calls();
         self.frame_read_calls = read_calls - self.last_total_read_calls;
         self.last_total_read_calls = read_calls;
 
+        #[cfg(feature = "read-metrics")]
+        {
+            self.frame_read_metrics = self.cs2.read_metrics.snapshot();
+        }
+
         Ok(())
     }
 
@@ -327,6 +416,46 @@ impl Application {
                 ]);
                 ui.text(text)
             }
+            let connection_text = match self.connection_state {
+                ConnectionState::Connecting => Some("Connecting..."),
+                ConnectionState::Loading => Some("Loading..."),
+                ConnectionState::Disconnected | ConnectionState::Connected => None,
+            };
+            if let Some(text) = connection_text {
+                ui.set_cursor_pos([
+                    ui.window_size()[0] - ui.calc_text_size(text)[0] - 10.0,
+                    80.0,
+                ]);
+                ui.text(text)
+            }
+            if let Some(tickrate) = self.perf_info.server_tickrate {
+                let text = format!("{:.0} Tick", tickrate);
+                ui.set_cursor_pos([
+                    ui.window_size()[0] - ui.calc_text_size(&text)[0] - 10.0,
+                    52.0,
+                ]);
+                ui.text(text)
+            }
+            #[cfg(feature = "read-metrics")]
+            {
+                let text = format!(
+                    "{} Reads in {:.2}ms",
+                    self.frame_read_metrics.total_calls(),
+                    self.frame_read_metrics.total_duration().as_secs_f32() * 1000.0
+                );
+                ui.set_cursor_pos([
+                    ui.window_size()[0] - ui.calc_text_size(&text)[0] - 10.0,
+                    66.0,
+                ]);
+                ui.text(text)
+            }
+        }
+
+        if self.connection_state != ConnectionState::Connected
+            || self.game_phase == Some(GamePhase::GameOver)
+        {
+            /* enhancements are not updated while loading or the match is over */
+            return;
         }
 
         for hack in self.enhancements.iter() {
@@ -366,9 +495,10 @@ fn main() {
 
     let command = args.command.as_ref().unwrap_or(&AppCommand::Overlay);
     let result = match command {
-        AppCommand::DumpSchema(args) => main_schema_dump(args),
-        AppCommand::Overlay => main_overlay(),
+        AppCommand::DumpSchema(dump_args) => main_schema_dump(&args, dump_args),
+        AppCommand::Overlay => main_overlay(&args),
         AppCommand::BHop => main_bhop(),
+        AppCommand::SelfTest => main_self_test(&args),
     };
 
     if let Err(error) = result {
@@ -383,6 +513,22 @@ struct AppArgs {
     #[clap(short, long)]
     verbose: bool,
 
+    /// Name of the kernel driver device (only required for renamed drivers)
+    #[clap(long)]
+    device_name: Option<String>,
+
+    /// Issue all memory reads from a dedicated thread
+    #[clap(long)]
+    dedicated_read_thread: bool,
+
+    /// Pin the dedicated read thread to the given cpu
+    #[clap(long)]
+    read_thread_cpu: Option<usize>,
+
+    /// Size (in bytes) of the windows used when scanning a module for patterns
+    #[clap(long)]
+    pattern_scan_chunk_size: Option<usize>,
+
     #[clap(subcommand)]
     command: Option<AppCommand>,
 }
@@ -396,6 +542,9 @@ enum AppCommand {
 
     /// Create a schema dump
     DumpSchema(SchemaDumpArgs),
+
+    /// Run a self test and print the report
+    SelfTest,
 }
 
 #[derive(Debug, Args)]
@@ -403,6 +552,17 @@ struct SchemaDumpArgs {
     pub target_file: PathBuf,
 }
 
+impl AppArgs {
+    fn create_options(&self) -> CreateOptions {
+        CreateOptions {
+            device_name: self.device_name.clone(),
+            dedicated_read_thread: self.dedicated_read_thread,
+            cpu_affinity: self.read_thread_cpu,
+            pattern_scan_chunk_size: self.pattern_scan_chunk_size,
+        }
+    }
+}
+
 fn is_console_invoked() -> bool {
     let console_count = unsafe {
         let mut result = [0u32; 128];
@@ -412,10 +572,10 @@ fn is_console_invoked() -> bool {
     console_count > 1
 }
 
-fn main_schema_dump(args: &SchemaDumpArgs) -> anyhow::Result<()> {
+fn main_schema_dump(app_args: &AppArgs, args: &SchemaDumpArgs) -> anyhow::Result<()> {
     log::info!("Dumping schema. Please wait...");
 
-    let cs2 = CS2Handle::create()?;
+    let cs2 = CS2Handle::create_with_options(&app_args.create_options())?;
     let schema = cs2::dump_schema(&cs2)?;
 
     let output = File::options()
@@ -430,11 +590,27 @@ fn main_schema_dump(args: &SchemaDumpArgs) -> anyhow::Result<()> {
     Ok(())
 }
 
+fn main_self_test(app_args: &AppArgs) -> anyhow::Result<()> {
+    let cs2 = CS2Handle::create_with_options(&app_args.create_options())?;
+    let report = cs2.self_test();
+
+    for line in report.to_string().lines() {
+        log::info!("{}", line);
+    }
+
+    if !report.passed() {
+        anyhow::bail!("self test failed");
+    }
+
+    log::info!("Self test passed");
+    Ok(())
+}
+
 fn main_bhop() -> anyhow::Result<()> {
     Ok(())
 }
 
-fn main_overlay() -> anyhow::Result<()> {
+fn main_overlay(args: &AppArgs) -> anyhow::Result<()> {
     let build_info = version_info()?;
     log::info!(
         "{} v{} ({}). Windows build {}.",
@@ -451,7 +627,7 @@ fn main_overlay() -> anyhow::Result<()> {
     }
 
     let settings = load_app_settings()?;
-    let cs2 = match CS2Handle::create() {
+    let cs2 = match CS2Handle::create_with_options(&args.create_options()) {
         Ok(handle) => handle,
         Err(err) => {
             if let Some(err) = err.downcast_ref::<KInterfaceError>() {
@@ -543,6 +719,22 @@ fn main_overlay() -> anyhow::Result<()> {
         overlay.imgui.load_ini_settings(&imgui_settings);
     }
 
+    let enhancements: Vec<(&'static str, Rc<RefCell<dyn Enhancement>>)> = vec![
+        ("player ESP", Rc::new(RefCell::new(PlayerESP::new()))),
+        (
+            "spectators list",
+            Rc::new(RefCell::new(SpectatorsList::new())),
+        ),
+        ("bomb info", Rc::new(RefCell::new(BombInfo::new()))),
+        (
+            "trigger bot",
+            Rc::new(RefCell::new(TriggerBot::new(LocalCrosshair::new(
+                cs2_offsets.offset_crosshair_id,
+            )))),
+        ),
+        ("recoil assist", Rc::new(RefCell::new(AntiAimPunsh::new()))),
+    ];
+
     let app = Application {
         fonts: app_fonts
             .borrow_mut()
@@ -570,21 +762,31 @@ fn main_overlay() -> anyhow::Result<()> {
             }
         }),
         class_name_cache: ClassNameCache::new(cs2.clone()),
+        player_name_cache: PlayerNameCache::new(),
         view_controller: ViewController::new(cs2_offsets.clone()),
-
-        enhancements: vec![
-            Rc::new(RefCell::new(PlayerESP::new())),
-            Rc::new(RefCell::new(SpectatorsList::new())),
-            Rc::new(RefCell::new(BombInfo::new())),
-            Rc::new(RefCell::new(TriggerBot::new(LocalCrosshair::new(
-                cs2_offsets.offset_crosshair_id,
-            )))),
-            Rc::new(RefCell::new(AntiAimPunsh::new())),
-        ],
+        last_local_controller: 0,
+
+        enhancement_budgets: enhancements
+            .iter()
+            .map(|&(name, _)| {
+                ErrorBudget::new(name, ENHANCEMENT_MAX_FAILURES, ENHANCEMENT_RETRY_INTERVAL)
+            })
+            .collect(),
+        enhancements: enhancements
+            .into_iter()
+            .map(|(_, enhancement)| enhancement)
+            .collect(),
 
         last_total_read_calls: 0,
         frame_read_calls: 0,
 
+        perf_info: Default::default(),
+        game_phase: None,
+        connection_state: ConnectionState::Disconnected,
+
+        #[cfg(feature = "read-metrics")]
+        frame_read_metrics: Default::default(),
+
         settings: settings.clone(),
         settings_visible: false,
         settings_dirty: false,
@@ -0,0 +1,145 @@
+use crate::UpdateContext;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MoneyState {
+    pub account: i32,
+    pub cash_spent_this_round: i32,
+}
+
+/// Read the money state of the local player.
+/// Returns None if we're currently not connected.
+pub fn read_local_money(ctx: &UpdateContext) -> anyhow::Result<Option<MoneyState>> {
+    let local_controller = match ctx
+        .cs2_entities
+        .get_local_player_controller()?
+        .try_reference_schema()?
+    {
+        Some(controller) => controller,
+        None => return Ok(None),
+    };
+
+    let money_services = match local_controller
+        .m_pInGameMoneyServices()?
+        .try_reference_schema()?
+    {
+        Some(services) => services,
+        None => return Ok(None),
+    };
+
+    Ok(Some(MoneyState {
+        account: money_services.m_iAccount()?,
+        cash_spent_this_round: money_services.m_iCashSpentThisRound()?,
+    }))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MoneyChangeKind {
+    /// Money has been spent on equipment
+    Purchase,
+
+    /// Any other change e.g. kill rewards, round rewards or refunds
+    Reward,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MoneyChange {
+    pub kind: MoneyChangeKind,
+    pub delta: i32,
+
+    /// Account balance after the change
+    pub account: i32,
+}
+
+/// Tracks the local players account and emits an event on every change.
+pub struct MoneyTracker {
+    last_state: Option<MoneyState>,
+}
+
+impl MoneyTracker {
+    pub fn new() -> Self {
+        Self { last_state: None }
+    }
+
+    /// Feed the current money state.
+    /// Passing None (e.g. while disconnected) resets the tracker,
+    /// so the next known state will not generate a change.
+    pub fn update(&mut self, state: Option<MoneyState>) -> Option<MoneyChange> {
+        let state = match state {
+            Some(state) => state,
+            None => {
+                self.last_state = None;
+                return None;
+            }
+        };
+
+        let last_state = self.last_state.replace(state)?;
+        let delta = state.account - last_state.account;
+        if delta == 0 {
+            return None;
+        }
+
+        let spent = state.cash_spent_this_round - last_state.cash_spent_this_round;
+        let kind = if delta < 0 && spent == -delta {
+            MoneyChangeKind::Purchase
+        } else {
+            MoneyChangeKind::Reward
+        };
+
+        Some(MoneyChange {
+            kind,
+            delta,
+            account: state.account,
+        })
+    }
+}
+
+/// Default value of mp_maxmoney.
+/// Used if the convar could not be resolved.
+const DEFAULT_MAX_MONEY: i32 = 16000;
+
+/* defaults of mp_team_cash_awards_loser_bonus, ..._loser_bonus_consecutive_rounds and mp_consecutive_loss_max */
+const LOSS_BONUS_BASE: i32 = 1400;
+const LOSS_BONUS_INCREMENT: i32 = 500;
+const LOSS_BONUS_MAX_LOSSES: i32 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EconomyState {
+    /// Max amount of money a player can have
+    pub max_money: i32,
+
+    /// Money the CTs will receive for losing the next round
+    pub ct_loss_bonus: i32,
+
+    /// Money the Ts will receive for losing the next round
+    pub t_loss_bonus: i32,
+}
+
+impl Default for EconomyState {
+    fn default() -> Self {
+        Self {
+            max_money: DEFAULT_MAX_MONEY,
+            ct_loss_bonus: LOSS_BONUS_BASE,
+            t_loss_bonus: LOSS_BONUS_BASE,
+        }
+    }
+}
+
+fn loss_bonus(consecutive_losses: i32) -> i32 {
+    LOSS_BONUS_BASE + LOSS_BONUS_INCREMENT * consecutive_losses.clamp(0, LOSS_BONUS_MAX_LOSSES)
+}
+
+/// Read the current economy state of both teams.
+/// Returns the default economy if no game rules are available.
+pub fn read_economy_state(ctx: &UpdateContext) -> anyhow::Result<EconomyState> {
+    let game_rules = match &ctx.game_rules {
+        Some(game_rules) => game_rules,
+        None => return Ok(EconomyState::default()),
+    };
+
+    let max_money = cs2::read_max_money(ctx.cs2, ctx.cs2_offsets)?.unwrap_or(DEFAULT_MAX_MONEY);
+    Ok(EconomyState {
+        max_money,
+        ct_loss_bonus: loss_bonus(game_rules.m_iNumConsecutiveCTLoses()?),
+        t_loss_bonus: loss_bonus(game_rules.m_iNumConsecutiveTerroristLoses()?),
+    })
+}
@@ -0,0 +1,75 @@
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+};
+
+use anyhow::Context;
+use cs2::EntitySystem;
+use cs2_schema_generated::{
+    cs2::client::CBasePlayerController,
+    EntityHandle,
+};
+
+use crate::utils::decode_fixed_cstr;
+
+/// Read the player name of the controller.
+pub fn read_player_name(controller: &CBasePlayerController) -> anyhow::Result<String> {
+    decode_fixed_cstr(&controller.m_iszPlayerName()?).context("player name")
+}
+
+struct CachedName {
+    serial_number: u32,
+    name: String,
+}
+
+/// Caches player names by their controller handle.
+/// The name will only be read again when the controller slot has been reused.
+pub struct PlayerNameCache {
+    cache: RefCell<BTreeMap<u32, CachedName>>,
+}
+
+impl PlayerNameCache {
+    pub fn new() -> Self {
+        Self {
+            cache: Default::default(),
+        }
+    }
+
+    /// Lookup the player name for the controller handle.
+    /// Returns None if the controller does not exist.
+    pub fn lookup(
+        &self,
+        cs2_entities: &EntitySystem,
+        handle: &EntityHandle<CBasePlayerController>,
+    ) -> anyhow::Result<Option<String>> {
+        let entity_index = handle.get_entity_index();
+        let serial_number = handle.get_serial_number();
+
+        if let Some(entry) = self.cache.borrow().get(&entity_index) {
+            if entry.serial_number == serial_number {
+                return Ok(Some(entry.name.clone()));
+            }
+        }
+
+        let controller = match cs2_entities.get_by_handle(handle)? {
+            Some(identity) => identity.entity()?.reference_schema()?,
+            None => return Ok(None),
+        };
+
+        let name = read_player_name(&controller)?;
+        self.cache.borrow_mut().insert(
+            entity_index,
+            CachedName {
+                serial_number,
+                name: name.clone(),
+            },
+        );
+
+        Ok(Some(name))
+    }
+
+    /// Invalidate all cached names (e.g. on map change)
+    pub fn clear(&self) {
+        self.cache.borrow_mut().clear();
+    }
+}
@@ -0,0 +1,66 @@
+use cs2::CSPlayerPawnEx;
+use cs2_schema_generated::cs2::client::C_CSWeaponBaseGun;
+
+use crate::{
+    enhancements::read_local_pawn,
+    weapon::{
+        WeaponId,
+        WEAPON_FLAG_TYPE_SNIPER_RIFLE,
+    },
+    UpdateContext,
+};
+
+fn local_player_scoped(ctx: &UpdateContext) -> anyhow::Result<bool> {
+    let local_pawn = match read_local_pawn(ctx)? {
+        Some(pawn) => pawn,
+        None => return Ok(false),
+    };
+
+    if !local_pawn.is_scoped()? {
+        return Ok(false);
+    }
+
+    if let Some(fov) = local_pawn.fov()? {
+        if fov > ctx.settings.esp_hide_while_scoped_max_fov {
+            return Ok(false);
+        }
+    }
+
+    if ctx.settings.esp_hide_while_scoped_sniper_only {
+        let weapon = match local_pawn
+            .m_pClippingWeapon()?
+            .cast::<C_CSWeaponBaseGun>()
+            .try_reference_schema()?
+        {
+            Some(weapon) => weapon,
+            None => return Ok(false),
+        };
+
+        let weapon_id = weapon
+            .m_AttributeManager()?
+            .m_Item()?
+            .m_iItemDefinitionIndex()?;
+        let weapon_id = WeaponId::from_id(weapon_id).unwrap_or(WeaponId::Unknown);
+        if (weapon_id.flags() & WEAPON_FLAG_TYPE_SNIPER_RIFLE) == 0 {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// Returns true if the overlay should be hidden because the local player is scoped in.
+/// The policy is controlled by the `esp_hide_while_scoped*` settings.
+pub fn should_suppress_overlay(ctx: &UpdateContext) -> bool {
+    if !ctx.settings.esp_hide_while_scoped {
+        return false;
+    }
+
+    match local_player_scoped(ctx) {
+        Ok(scoped) => scoped,
+        Err(error) => {
+            log::warn!("Failed to read local scope state: {:#}", error);
+            false
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use crate::UpdateContext;
+
+/// Steam account type of an individual user account
+const STEAM_ACCOUNT_TYPE_INDIVIDUAL: u64 = 1;
+
+/// Validate a raw 64 bit steam id as read from the player controller.
+///
+/// The steam id 64 is encoded as `universe (8 bit) | account type (4 bit) | instance (20 bit) | account id (32 bit)`.
+/// Bots and unconnected players have an id of zero, which results in `None`.
+pub fn parse_steam_id(raw: u64) -> Option<u64> {
+    let account_id = raw & 0xFFFF_FFFF;
+    let account_type = (raw >> 52) & 0x0F;
+    if account_id == 0 || account_type != STEAM_ACCOUNT_TYPE_INDIVIDUAL {
+        return None;
+    }
+
+    Some(raw)
+}
+
+/// Get the 32 bit account id (also known as steam id 3) from a steam id 64.
+pub fn steam_account_id(steam_id: u64) -> u32 {
+    (steam_id & 0xFFFF_FFFF) as u32
+}
+
+/// Read the steam id of the local player.
+/// Returns None if we're currently not in a game.
+///
+/// Note:
+/// The match/lobby id is not exposed by the client schema and therefore not available.
+pub fn read_local_steam_id(ctx: &UpdateContext) -> anyhow::Result<Option<u64>> {
+    let local_controller = match ctx
+        .cs2_entities
+        .get_local_player_controller()?
+        .try_reference_schema()?
+    {
+        Some(controller) => controller,
+        None => return Ok(None),
+    };
+
+    Ok(parse_steam_id(local_controller.m_steamID()?))
+}
@@ -130,6 +130,15 @@ pub struct AppSettings {
     #[serde(default = "default_esp_line_position")]
     pub esp_lines_position: LineStartPosition,
 
+    #[serde(default = "bool_false")]
+    pub esp_hide_while_scoped: bool,
+
+    #[serde(default = "bool_true")]
+    pub esp_hide_while_scoped_sniper_only: bool,
+
+    #[serde(default = "default_u32::<60>")]
+    pub esp_hide_while_scoped_max_fov: u32,
+
     #[serde(default = "bool_true")]
     pub bomb_timer: bool,
 
@@ -150,6 +150,12 @@ impl SettingsUI {
                                 }
                             }
 
+                            ui.checkbox(obfstr!("Hide ESP while scoped"), &mut settings.esp_hide_while_scoped);
+                            if settings.esp_hide_while_scoped {
+                                ui.checkbox(obfstr!("Only with sniper rifles"), &mut settings.esp_hide_while_scoped_sniper_only);
+                                ui.slider(obfstr!("Max FOV"), 1, 90, &mut settings.esp_hide_while_scoped_max_fov);
+                            }
+
                             ui.checkbox(obfstr!("ESP Team"), &mut settings.esp_enabled_team);
                             if settings.esp_enabled_team {
                                 ui.same_line();
@@ -0,0 +1,198 @@
+use std::time::Duration;
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::{
+    enhancements::{
+        read_all_players,
+        BombInfo,
+        C4Info,
+        PlayerInfo,
+    },
+    grenades::{
+        self,
+        GrenadeInfo,
+        GrenadeKind,
+        InfernoInfo,
+    },
+    utils::ErrorBudget,
+    UpdateContext,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoneSnapshot {
+    pub name: String,
+    pub parent: Option<usize>,
+    pub flags: u32,
+    pub position: [f32; 3],
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerSnapshot {
+    pub controller_entity_id: u32,
+    pub team_id: u8,
+
+    pub player_health: i32,
+    pub player_armor: i32,
+    pub player_has_defuser: bool,
+    pub player_on_ground: bool,
+    pub player_name: String,
+    pub player_place_name: Option<String>,
+    pub weapon: String,
+
+    pub position: [f32; 3],
+    pub bones: Vec<BoneSnapshot>,
+
+    pub distance: Option<f32>,
+    pub is_visible: bool,
+}
+
+impl From<&PlayerInfo> for PlayerSnapshot {
+    fn from(value: &PlayerInfo) -> Self {
+        Self {
+            controller_entity_id: value.controller_entity_id,
+            team_id: value.team_id,
+
+            player_health: value.player_health,
+            player_armor: value.player_armor,
+            player_has_defuser: value.player_has_defuser,
+            player_on_ground: value.player_on_ground,
+            player_name: value.player_name.clone(),
+            player_place_name: value.player_place_name.clone(),
+            weapon: value.weapon.display_name_with_state(&value.weapon_state),
+
+            position: value.position.into(),
+            bones: value
+                .model
+                .bones
+                .iter()
+                .zip(value.bone_states.iter())
+                .map(|(bone, state)| BoneSnapshot {
+                    name: bone.name.clone(),
+                    parent: bone.parent,
+                    flags: bone.flags,
+                    position: state.position.into(),
+                })
+                .collect(),
+
+            distance: value.distance,
+            is_visible: value.is_visible,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrenadeSnapshot {
+    pub kind: GrenadeKind,
+    pub position: [f32; 3],
+    pub owner_team: Option<u8>,
+    pub owner_name: Option<String>,
+}
+
+impl From<&GrenadeInfo> for GrenadeSnapshot {
+    fn from(value: &GrenadeInfo) -> Self {
+        Self {
+            kind: value.kind,
+            position: value.position.into(),
+            owner_team: value.owner_team,
+            owner_name: value.owner_name.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InfernoSnapshot {
+    pub position: [f32; 3],
+    pub owner_team: Option<u8>,
+    pub owner_name: Option<String>,
+    pub remaining_time: f32,
+}
+
+impl From<&InfernoInfo> for InfernoSnapshot {
+    fn from(value: &InfernoInfo) -> Self {
+        Self {
+            position: value.position.into(),
+            owner_team: value.owner_team,
+            owner_name: value.owner_name.clone(),
+            remaining_time: value.remaining_time,
+        }
+    }
+}
+
+/// Plain data snapshot of the current game state.
+/// The snapshot does not hold any references into the game memory
+/// and can therefore be (de)serialized e.g. for bug reports.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenderSnapshot {
+    pub players: Vec<PlayerSnapshot>,
+    pub bomb: Option<C4Info>,
+    pub grenades: Vec<GrenadeSnapshot>,
+    pub infernos: Vec<InfernoSnapshot>,
+}
+
+/// Consecutive failures after which a sub reader will be disabled
+const SNAPSHOT_READER_MAX_FAILURES: usize = 10;
+
+/// Time after which a disabled sub reader will be retried
+const SNAPSHOT_READER_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Builds render snapshots.
+/// A sub reader which fails repeatedly will be disabled for some time
+/// so it does not degrade the whole snapshot.
+pub struct RenderSnapshotBuilder {
+    players: ErrorBudget,
+    bomb: ErrorBudget,
+    grenades: ErrorBudget,
+    infernos: ErrorBudget,
+}
+
+impl RenderSnapshotBuilder {
+    pub fn new() -> Self {
+        let budget = |name| {
+            ErrorBudget::new(
+                name,
+                SNAPSHOT_READER_MAX_FAILURES,
+                SNAPSHOT_READER_RETRY_INTERVAL,
+            )
+        };
+
+        Self {
+            players: budget("player reader"),
+            bomb: budget("bomb reader"),
+            grenades: budget("grenade reader"),
+            infernos: budget("inferno reader"),
+        }
+    }
+
+    pub fn build_render_snapshot(&mut self, ctx: &UpdateContext) -> RenderSnapshot {
+        let players = self
+            .players
+            .execute(|| read_all_players(ctx))
+            .unwrap_or_default();
+
+        RenderSnapshot {
+            players: players.iter().map(PlayerSnapshot::from).collect(),
+            bomb: self
+                .bomb
+                .execute(|| BombInfo::read_state(ctx))
+                .unwrap_or_default(),
+            grenades: self
+                .grenades
+                .execute(|| grenades::read_grenades(ctx))
+                .unwrap_or_default()
+                .iter()
+                .map(GrenadeSnapshot::from)
+                .collect(),
+            infernos: self
+                .infernos
+                .execute(|| grenades::read_infernos(ctx))
+                .unwrap_or_default()
+                .iter()
+                .map(InfernoSnapshot::from)
+                .collect(),
+        }
+    }
+}
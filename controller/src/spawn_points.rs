@@ -0,0 +1,64 @@
+use anyhow::Context;
+use cs2::{
+    read_origin,
+    CEntityIdentityEx,
+};
+use cs2_schema_generated::cs2::client::C_BaseEntity;
+
+use crate::UpdateContext;
+
+pub const TEAM_TERRORIST: u8 = 2;
+pub const TEAM_COUNTER_TERRORIST: u8 = 3;
+
+#[derive(Debug, Clone)]
+pub struct SpawnPointInfo {
+    pub position: nalgebra::Vector3<f32>,
+    pub team: u8,
+}
+
+/// Map the spawn point class name to the team it belongs to.
+fn spawn_point_team(class_name: &str) -> Option<u8> {
+    match class_name {
+        "C_InfoPlayerTerrorist" | "CInfoPlayerTerrorist" => Some(TEAM_TERRORIST),
+        "C_InfoPlayerCounterterrorist" | "CInfoPlayerCounterterrorist" => {
+            Some(TEAM_COUNTER_TERRORIST)
+        }
+        _ => None,
+    }
+}
+
+/// Read all team specific spawn points.
+///
+/// Note:
+/// Spawn points are usually only present on the server and will therefore only be
+/// available if the map networks them to the client. An empty list will be returned otherwise.
+pub fn read_spawn_points(ctx: &UpdateContext) -> anyhow::Result<Vec<SpawnPointInfo>> {
+    let mut result = Vec::new();
+
+    for entity_identity in ctx.cs2_entities.all_identities() {
+        let class_name = ctx
+            .class_name_cache
+            .lookup(&entity_identity.entity_class_info()?)
+            .context("class name")?;
+
+        let team = match class_name.and_then(|name| spawn_point_team(name)) {
+            Some(team) => team,
+            None => continue,
+        };
+
+        let entity = entity_identity
+            .entity_ptr::<C_BaseEntity>()?
+            .read_schema()
+            .context("spawn point schema")?;
+
+        let position = match read_origin(&entity)? {
+            Some(origin) => nalgebra::Vector3::<f32>::from_column_slice(&origin),
+            /* entity is still being created */
+            None => continue,
+        };
+
+        result.push(SpawnPointInfo { position, team });
+    }
+
+    Ok(result)
+}
@@ -0,0 +1,54 @@
+use crate::{
+    player_name_cache::read_player_name,
+    UpdateContext,
+};
+
+#[derive(Debug, Clone)]
+pub struct TeammateStatus {
+    pub player_name: String,
+    pub health: u32,
+    pub alive: bool,
+}
+
+/// Read the status of all teammates (excluding the local player).
+/// The status is read from the player controllers, hence it is available
+/// even if the teammate pawn is dormant.
+pub fn read_team_status(ctx: &UpdateContext) -> anyhow::Result<Vec<TeammateStatus>> {
+    let local_controller_ptr = ctx.cs2_entities.get_local_player_controller()?;
+    let local_controller = match local_controller_ptr.try_reference_schema()? {
+        Some(controller) => controller,
+        None => return Ok(Vec::new()),
+    };
+
+    let local_team = local_controller.m_iTeamNum()?;
+    let local_controller_address = local_controller_ptr.address()?;
+
+    let mut result = Vec::with_capacity(8);
+    for controller in ctx.cs2_entities.get_player_controllers()? {
+        if controller.address()? == local_controller_address {
+            continue;
+        }
+
+        let controller = match controller.try_read_schema()? {
+            Some(controller) => controller,
+            None => continue,
+        };
+
+        if controller.m_iTeamNum()? != local_team {
+            continue;
+        }
+
+        let alive = controller.m_bPawnIsAlive()?;
+        result.push(TeammateStatus {
+            player_name: read_player_name(&controller)?,
+            health: if alive {
+                controller.m_iPawnHealth()?
+            } else {
+                0
+            },
+            alive,
+        });
+    }
+
+    Ok(result)
+}
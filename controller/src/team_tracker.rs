@@ -0,0 +1,74 @@
+use std::collections::BTreeMap;
+
+use crate::enhancements::PlayerInfo;
+
+/// Stable identity of a player across team changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PlayerIdentity {
+    SteamId(u64),
+
+    /// Bots do not have a steam id, hence we fall back to the controller entity index
+    Controller(u32),
+}
+
+impl PlayerIdentity {
+    pub fn from_player(player: &PlayerInfo) -> Self {
+        match player.steam_id {
+            Some(steam_id) => Self::SteamId(steam_id),
+            None => Self::Controller(player.controller_entity_id),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TeamSwitch {
+    pub identity: PlayerIdentity,
+    pub player_name: String,
+
+    pub old_team: u8,
+    pub new_team: u8,
+}
+
+/// Tracks the team of every player and emits an event
+/// when a player changes their team (e.g. halftime swap or autoteam).
+pub struct TeamTracker {
+    teams: BTreeMap<PlayerIdentity, u8>,
+}
+
+impl TeamTracker {
+    pub fn new() -> Self {
+        Self {
+            teams: Default::default(),
+        }
+    }
+
+    /// Feed the currently known players.
+    /// Players which are not present (e.g. dead or dormant) keep their last known team.
+    pub fn update(&mut self, players: &[PlayerInfo]) -> Vec<TeamSwitch> {
+        let mut switches = Vec::new();
+        for player in players.iter() {
+            let identity = PlayerIdentity::from_player(player);
+            let old_team = match self.teams.insert(identity, player.team_id) {
+                Some(team) => team,
+                None => continue,
+            };
+
+            if old_team != player.team_id {
+                switches.push(TeamSwitch {
+                    identity,
+                    player_name: player.player_name.clone(),
+
+                    old_team,
+                    new_team: player.team_id,
+                });
+            }
+        }
+
+        switches
+    }
+
+    /// Forget all known players e.g. when disconnecting from the server.
+    pub fn reset(&mut self) {
+        self.teams.clear();
+    }
+}
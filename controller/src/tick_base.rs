@@ -0,0 +1,36 @@
+use cs2::Globals;
+
+use crate::UpdateContext;
+
+/// Read the tick base of the local player controller.
+/// Returns None if we're currently not connected or have not yet spawned.
+pub fn read_tick_base(ctx: &UpdateContext) -> anyhow::Result<Option<i32>> {
+    let local_controller = match ctx
+        .cs2_entities
+        .get_local_player_controller()?
+        .try_reference_schema()?
+    {
+        Some(controller) => controller,
+        None => return Ok(None),
+    };
+
+    let tick_base = local_controller.m_nTickBase()?;
+    if tick_base == 0 {
+        /* not yet spawned */
+        return Ok(None);
+    }
+
+    Ok(Some(tick_base as i32))
+}
+
+/// Calculate the drift (in ticks) between the tick base and the current server tick.
+/// The server tick is derived from the current game time.
+pub fn tick_base_drift(tick_base: i32, globals: &Globals) -> anyhow::Result<i32> {
+    let tick_interval = globals.tick_interval()?;
+    if !tick_interval.is_finite() || tick_interval <= 0.0 {
+        anyhow::bail!("invalid tick interval {}", tick_interval);
+    }
+
+    let server_tick = (globals.cur_time()? / tick_interval).round() as i32;
+    Ok(tick_base - server_tick)
+}
@@ -0,0 +1,71 @@
+use std::time::{
+    Duration,
+    Instant,
+};
+
+/// Disables a reader after too many consecutive failures
+/// and retries it again after a cool down.
+pub struct ErrorBudget {
+    name: &'static str,
+    max_failures: usize,
+    retry_interval: Duration,
+
+    consecutive_failures: usize,
+    disabled_until: Option<Instant>,
+}
+
+impl ErrorBudget {
+    pub fn new(name: &'static str, max_failures: usize, retry_interval: Duration) -> Self {
+        Self {
+            name,
+            max_failures,
+            retry_interval,
+
+            consecutive_failures: 0,
+            disabled_until: None,
+        }
+    }
+
+    pub fn is_disabled(&self) -> bool {
+        self.disabled_until
+            .map(|timeout| timeout > Instant::now())
+            .unwrap_or(false)
+    }
+
+    /// Execute the reader unless it has been disabled.
+    /// Returns None if the reader is disabled or failed.
+    pub fn execute<T>(&mut self, reader: impl FnOnce() -> anyhow::Result<T>) -> Option<T> {
+        if self.is_disabled() {
+            return None;
+        }
+
+        match reader() {
+            Ok(value) => {
+                if self.disabled_until.take().is_some() {
+                    log::info!("{} recovered and has been enabled again", self.name);
+                }
+
+                self.consecutive_failures = 0;
+                Some(value)
+            }
+            Err(error) => {
+                self.consecutive_failures += 1;
+                if self.disabled_until.is_some() {
+                    /* retry failed, the failure has already been logged */
+                    self.disabled_until = Some(Instant::now() + self.retry_interval);
+                } else if self.consecutive_failures >= self.max_failures {
+                    log::warn!(
+                        "{} failed {} times in a row. Disabling it for {:?}: {:#}",
+                        self.name,
+                        self.consecutive_failures,
+                        self.retry_interval,
+                        error
+                    );
+                    self.disabled_until = Some(Instant::now() + self.retry_interval);
+                }
+
+                None
+            }
+        }
+    }
+}
@@ -1,2 +1,8 @@
 mod imgui;
 pub use self::imgui::*;
+
+mod string;
+pub use string::*;
+
+mod error_budget;
+pub use error_budget::*;
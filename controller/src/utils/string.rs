@@ -0,0 +1,10 @@
+use std::ffi::CStr;
+
+use anyhow::Context;
+
+/// Decode a fixed size, nul terminated char buffer.
+/// Invalid UTF-8 sequences will be replaced.
+pub fn decode_fixed_cstr(buffer: &[u8]) -> anyhow::Result<String> {
+    let value = CStr::from_bytes_until_nul(buffer).context("missing nul terminator")?;
+    Ok(value.to_string_lossy().to_string())
+}
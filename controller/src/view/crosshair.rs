@@ -1,13 +1,75 @@
 use std::time::Instant;
 
 use anyhow::Context;
-use cs2::CEntityIdentityEx;
+use cs2::{
+    view_direction,
+    CEntityIdentityEx,
+    CSPlayerPawnEx,
+};
 use cs2_schema_generated::{
     cs2::client::CEntityInstance,
     EntityHandle,
 };
 
-use crate::UpdateContext;
+use super::ViewController;
+use crate::{
+    enhancements::read_local_pawn,
+    UpdateContext,
+};
+
+/// The visual recoil equals the aim punch scaled by weapon_recoil_scale
+const WEAPON_RECOIL_SCALE: f32 = 2.0;
+
+/// Distance (in units) of the projected aim point in front of the eyes
+const AIM_POINT_DISTANCE: f32 = 1000.0;
+
+fn local_aim_point(
+    ctx: &UpdateContext,
+    view: &ViewController,
+) -> anyhow::Result<Option<mint::Vector2<f32>>> {
+    let local_pawn = match read_local_pawn(ctx)? {
+        Some(pawn) => pawn,
+        None => return Ok(None),
+    };
+
+    let eye_position = match local_pawn.eye_position()? {
+        Some(position) => position,
+        None => return Ok(None),
+    };
+
+    let eye_angles = local_pawn.m_angEyeAngles()?;
+    let aim_punch = local_pawn.m_aimPunchAngle()?;
+    let aim_punch = if aim_punch[0].is_finite() && aim_punch[1].is_finite() {
+        aim_punch
+    } else {
+        [0.0; 4]
+    };
+
+    let direction = view_direction([
+        eye_angles[0] + aim_punch[0] * WEAPON_RECOIL_SCALE,
+        eye_angles[1] + aim_punch[1] * WEAPON_RECOIL_SCALE,
+    ]);
+
+    Ok(view.world_to_screen(&(eye_position + direction * AIM_POINT_DISTANCE), true))
+}
+
+/// Screen position the local player is actually aiming at, including the recoil (aim punch).
+/// Returns the screen center if the aim point could not be determined.
+pub fn read_local_aim_point(ctx: &UpdateContext, view: &ViewController) -> mint::Vector2<f32> {
+    let screen_center = mint::Vector2 {
+        x: view.screen_bounds.x / 2.0,
+        y: view.screen_bounds.y / 2.0,
+    };
+
+    match local_aim_point(ctx, view) {
+        Ok(Some(position)) => position,
+        Ok(None) => screen_center,
+        Err(error) => {
+            log::warn!("Failed to read local aim point: {:#}", error);
+            screen_center
+        }
+    }
+}
 
 #[derive(Debug)]
 pub struct CrosshairTarget {
@@ -1,3 +1,13 @@
+use cs2::CSPlayerPawnEx;
+use cs2_schema_generated::cs2::client::{
+    CPlayer_MovementServices_Humanoid,
+    C_BasePlayerWeapon,
+    C_CSPlayerPawn,
+    C_CSWeaponBase,
+    C_CSWeaponBaseGun,
+    C_EconEntity,
+};
+
 pub const WEAPON_FLAG_TYPE_KNIFE: u32 = 0x01;
 pub const WEAPON_FLAG_TYPE_PISTOL: u32 = 0x02;
 pub const WEAPON_FLAG_TYPE_SHOTGUN: u32 = 0x04;
@@ -7,6 +17,110 @@ pub const WEAPON_FLAG_TYPE_SNIPER_RIFLE: u32 = 0x20;
 pub const WEAPON_FLAG_TYPE_MACHINE_GUN: u32 = 0x40;
 pub const WEAPON_FLAG_TYPE_GRANADE: u32 = 0x80;
 
+/// Current attachment/fire mode state of a weapon
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeaponState {
+    pub silencer_on: bool,
+    pub burst_mode: bool,
+    pub zoom_level: i32,
+}
+
+impl Default for WeaponState {
+    fn default() -> Self {
+        Self {
+            /* silencer weapons are silenced by default */
+            silencer_on: true,
+            burst_mode: false,
+            zoom_level: 0,
+        }
+    }
+}
+
+/// Fraction of the weapons max speed up to which shots are accurate while standing
+const ACCURATE_SPEED_FACTOR: f32 = 0.34;
+
+/// Fraction of the weapons max speed up to which shots are accurate while crouching.
+/// This is a heuristic as crouching reduces the movement inaccuracy.
+const ACCURATE_SPEED_FACTOR_CROUCHED: f32 = 0.40;
+
+/// Cosmetic skin (paint kit) applied to a weapon
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SkinInfo {
+    pub paint_kit: i32,
+    pub wear: f32,
+    pub seed: i32,
+}
+
+/// Read the fallback paint kit of the weapon.
+/// Returns `None` if the weapon uses the default paint kit.
+pub fn read_weapon_skin(weapon: &C_EconEntity) -> anyhow::Result<Option<SkinInfo>> {
+    let paint_kit = weapon.m_nFallbackPaintKit()?;
+    if paint_kit <= 0 {
+        return Ok(None);
+    }
+
+    Ok(Some(SkinInfo {
+        paint_kit,
+        wear: weapon.m_flFallbackWear()?,
+        seed: weapon.m_nFallbackSeed()?,
+    }))
+}
+
+/// Read the StatTrak kill count of the weapon.
+/// Returns `None` if the weapon has no StatTrak counter.
+pub fn read_weapon_stat_trak(weapon: &C_EconEntity) -> anyhow::Result<Option<i32>> {
+    /* weapons without StatTrak have a counter of -1 */
+    let stat_trak = weapon.m_nFallbackStatTrak()?;
+    Ok(Some(stat_trak).filter(|count| *count >= 0))
+}
+
+/// Ammunition of a weapon
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeaponAmmo {
+    /// Rounds in the current clip
+    pub clip: i32,
+
+    /// Primary reserve ammunition
+    pub reserve: i32,
+}
+
+/// Read the clip and reserve ammunition of the weapon.
+/// Returns `None` if the weapon does not use a clip (e.g. knifes).
+pub fn read_weapon_ammo(weapon: &C_BasePlayerWeapon) -> anyhow::Result<Option<WeaponAmmo>> {
+    /* weapons without a clip have a clip value of -1 */
+    let clip = weapon.m_iClip1()?;
+    if clip < 0 {
+        return Ok(None);
+    }
+
+    Ok(Some(WeaponAmmo {
+        clip,
+        reserve: weapon.m_pReserveAmmo()?[0],
+    }))
+}
+
+/// Check whether the weapon has to be reloaded soon.
+/// This is the case when the clip contains at most `clip_threshold` rounds
+/// and there is reserve ammunition left to reload.
+/// Knifes and grenades never need to be reloaded.
+pub fn must_reload_soon(weapon: &C_CSWeaponBase, clip_threshold: i32) -> anyhow::Result<bool> {
+    let weapon_id = weapon
+        .m_AttributeManager()?
+        .m_Item()?
+        .m_iItemDefinitionIndex()?;
+    let weapon_id = WeaponId::from_id(weapon_id).unwrap_or(WeaponId::Unknown);
+    if (weapon_id.flags() & (WEAPON_FLAG_TYPE_KNIFE | WEAPON_FLAG_TYPE_GRANADE)) > 0 {
+        return Ok(false);
+    }
+
+    let ammo = match read_weapon_ammo(weapon)? {
+        Some(ammo) => ammo,
+        None => return Ok(false),
+    };
+
+    Ok(ammo.clip <= clip_threshold && ammo.reserve > 0)
+}
+
 macro_rules! define_weapons {
     (
         $(#[$struct_meta:meta])*
@@ -124,3 +238,124 @@ define_weapons! {
         KnifesSkeleton { id: 525, name: "Knife (Skeleton)", flags: WEAPON_FLAG_TYPE_KNIFE },
     }
 }
+
+impl WeaponId {
+    /// Max player movement speed (units per second) while holding the weapon (unscoped)
+    pub fn max_speed(&self) -> f32 {
+        match self {
+            Self::Deagle => 230.0,
+            Self::Revolver => 220.0,
+
+            Self::Ak47 | Self::Galilar => 215.0,
+            Self::M4A4 | Self::M4A1Silencer => 225.0,
+            Self::Famas | Self::Aug => 220.0,
+            Self::Sg553 => 210.0,
+
+            Self::AWP => 200.0,
+            Self::Ssg08 => 230.0,
+            Self::G3SG1 | Self::Scar20 => 215.0,
+
+            Self::MP7 | Self::Taser => 220.0,
+            Self::MP5SD => 235.0,
+            Self::Ump45 | Self::P90 => 230.0,
+
+            Self::Nova => 220.0,
+            Self::XM1014 => 215.0,
+            Self::Mag7 => 225.0,
+            Self::SawedOff => 210.0,
+
+            Self::M249 => 195.0,
+            Self::Negev => 150.0,
+
+            _ if (self.flags() & WEAPON_FLAG_TYPE_KNIFE) > 0 => 250.0,
+            Self::C4 | Self::Healthshot => 250.0,
+            _ if (self.flags() & WEAPON_FLAG_TYPE_GRANADE) > 0 => 245.0,
+
+            /* pistols and the remaining SMGs */
+            _ => 240.0,
+        }
+    }
+
+    /// Max movement speed (units per second) at which shots are still accurate
+    pub fn accurate_speed(&self, crouched: bool) -> f32 {
+        let factor = if crouched {
+            ACCURATE_SPEED_FACTOR_CROUCHED
+        } else {
+            ACCURATE_SPEED_FACTOR
+        };
+
+        self.max_speed() * factor
+    }
+
+    pub fn has_silencer(&self) -> bool {
+        matches!(self, Self::M4A1Silencer | Self::USPS)
+    }
+
+    pub fn has_burst_mode(&self) -> bool {
+        matches!(self, Self::Glock | Self::Famas)
+    }
+
+    pub fn has_zoom(&self) -> bool {
+        (self.flags() & WEAPON_FLAG_TYPE_SNIPER_RIFLE) > 0
+            || matches!(self, Self::Aug | Self::Sg553)
+    }
+
+    /// Display name including the non default weapon state
+    /// e.g. "M4A1-S (unsilenced)"
+    pub fn display_name_with_state(&self, state: &WeaponState) -> String {
+        let mut modifiers = Vec::new();
+        if self.has_silencer() && !state.silencer_on {
+            modifiers.push("unsilenced");
+        }
+
+        if self.has_burst_mode() && state.burst_mode {
+            modifiers.push("burst");
+        }
+
+        if self.has_zoom() && state.zoom_level > 0 {
+            modifiers.push("scoped");
+        }
+
+        if modifiers.is_empty() {
+            self.display_name().to_string()
+        } else {
+            format!("{} ({})", self.display_name(), modifiers.join(", "))
+        }
+    }
+}
+
+/// Check whether the pawn moves slow enough to shoot accurately with the current weapon.
+/// Airborne pawns are never accurate. Pawns without a weapon are always considered accurate.
+pub fn movement_accuracy_ok(pawn: &C_CSPlayerPawn) -> anyhow::Result<bool> {
+    if !pawn.is_on_ground()? {
+        return Ok(false);
+    }
+
+    let weapon = match pawn
+        .m_pClippingWeapon()?
+        .cast::<C_CSWeaponBaseGun>()
+        .try_reference_schema()?
+    {
+        Some(weapon) => weapon,
+        None => return Ok(true),
+    };
+
+    let weapon_id = weapon
+        .m_AttributeManager()?
+        .m_Item()?
+        .m_iItemDefinitionIndex()?;
+    let weapon_id = WeaponId::from_id(weapon_id).unwrap_or(WeaponId::Unknown);
+
+    let crouched = match pawn
+        .m_pMovementServices()?
+        .cast::<CPlayer_MovementServices_Humanoid>()
+        .try_reference_schema()?
+    {
+        Some(movement_services) => movement_services.m_bDucked()?,
+        None => false,
+    };
+
+    let velocity = pawn.m_vecAbsVelocity()?;
+    let speed = velocity[0].hypot(velocity[1]);
+    Ok(speed <= weapon_id.accurate_speed(crouched))
+}
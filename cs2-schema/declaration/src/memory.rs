@@ -95,3 +95,82 @@ impl MemoryHandle {
         T::from_memory(self.clone().with_offset(offset)?)
     }
 }
+
+/// Memory driver backed by a local byte buffer.
+///
+/// This allows constructing `MemoryHandle`s and parsing schema values
+/// without a running CS2 process (e.g. from captured memory).
+pub struct VecMemoryDriver {
+    base_address: u64,
+    buffer: Vec<u8>,
+}
+
+impl VecMemoryDriver {
+    pub fn new(base_address: u64, buffer: Vec<u8>) -> Self {
+        Self {
+            base_address,
+            buffer,
+        }
+    }
+
+    pub fn base_address(&self) -> u64 {
+        self.base_address
+    }
+
+    pub fn buffer(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Reference the memory at the given address.
+    pub fn reference_memory(&self, address: u64, length: usize) -> anyhow::Result<&[u8]> {
+        if address < self.base_address {
+            anyhow::bail!("invalid target memory address")
+        }
+
+        let offset = (address - self.base_address) as usize;
+        if offset + length > self.buffer.len() {
+            anyhow::bail!("invalid target memory address")
+        }
+
+        Ok(&self.buffer[offset..offset + length])
+    }
+
+    /// Create a memory handle pointing to the start of the buffer.
+    pub fn into_handle(self) -> MemoryHandle {
+        let address = self.base_address;
+        let driver: Arc<dyn MemoryDriver> = Arc::new(self);
+        MemoryHandle::from_driver(&driver, address)
+    }
+}
+
+impl MemoryDriver for VecMemoryDriver {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn read_slice(&self, address: u64, slice: &mut [u8]) -> anyhow::Result<()> {
+        slice.copy_from_slice(self.reference_memory(address, slice.len())?);
+        Ok(())
+    }
+
+    fn read_cstring(
+        &self,
+        address: u64,
+        _expected_length: Option<usize>,
+        max_length: Option<usize>,
+    ) -> anyhow::Result<String> {
+        /* validate the start address */
+        self.reference_memory(address, 0)?;
+
+        let available = self.buffer.len() - (address - self.base_address) as usize;
+        let length = max_length.unwrap_or(available).min(available);
+
+        let memory = self.reference_memory(address, length)?;
+        let length = memory
+            .iter()
+            .position(|value| *value == 0)
+            .ok_or_else(|| anyhow::anyhow!("missing nul terminator"))?;
+
+        Ok(String::from_utf8_lossy(&memory[..length]).to_string())
+    }
+}
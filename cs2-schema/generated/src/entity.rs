@@ -25,17 +25,29 @@ impl<T> EntityHandle<T> {
         }
     }
 
+    /// Index of the entity within the entity list (lower 15 bits)
     pub fn get_entity_index(&self) -> u32 {
         self.value & 0x7FFF
     }
 
+    /// Entity index usable for indexing/keying local collections
+    pub fn index(&self) -> usize {
+        self.get_entity_index() as usize
+    }
+
     pub fn is_valid(&self) -> bool {
         self.get_entity_index() < 0x7FF0
     }
 
+    /// Serial number of the entity slot (upper 17 bits).
+    /// The serial number changes whenever the entity slot gets reused.
     pub fn get_serial_number(&self) -> u32 {
         self.value >> 15
     }
+
+    pub fn serial(&self) -> u32 {
+        self.get_serial_number()
+    }
 }
 
 impl<T> Debug for EntityHandle<T> {
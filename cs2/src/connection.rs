@@ -0,0 +1,57 @@
+use crate::{
+    CS2Handle,
+    CS2Offsets,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ConnectionState {
+    Disconnected,
+    Connecting,
+
+    /// Connected to the server but the map is still loading.
+    /// Entity data should not be trusted yet.
+    Loading,
+
+    Connected,
+}
+
+/* values of CNetworkGameClient's signon state */
+const SIGNONSTATE_NONE: i32 = 0;
+const SIGNONSTATE_CHALLENGE: i32 = 1;
+const SIGNONSTATE_CONNECTED: i32 = 2;
+const SIGNONSTATE_FULL: i32 = 6;
+
+impl ConnectionState {
+    pub fn from_signon_state(signon_state: i32) -> Self {
+        match signon_state {
+            SIGNONSTATE_NONE => Self::Disconnected,
+            SIGNONSTATE_CHALLENGE | SIGNONSTATE_CONNECTED => Self::Connecting,
+            SIGNONSTATE_FULL => Self::Connected,
+            /* new, prespawn, spawn and changelevel */
+            _ => Self::Loading,
+        }
+    }
+}
+
+/// Read the connection state of the network game client.
+/// Always reports `ConnectionState::Connected` if the required offsets could not be resolved.
+pub fn read_connection_state(
+    cs2: &CS2Handle,
+    offsets: &CS2Offsets,
+) -> anyhow::Result<ConnectionState> {
+    let (network_game_client, offset_signon_state) =
+        match (offsets.network_game_client, offsets.offset_signon_state) {
+            (Some(network_game_client), Some(offset_signon_state)) => {
+                (network_game_client, offset_signon_state)
+            }
+            _ => return Ok(ConnectionState::Connected),
+        };
+
+    let network_game_client = cs2.read_sized::<u64>(&[network_game_client])?;
+    if network_game_client == 0 {
+        return Ok(ConnectionState::Disconnected);
+    }
+
+    let signon_state = cs2.read_sized::<i32>(&[network_game_client + offset_signon_state])?;
+    Ok(ConnectionState::from_signon_state(signon_state))
+}
@@ -0,0 +1,18 @@
+use crate::{
+    offsets_manual::tier0::CConVarBaseData,
+    CS2Handle,
+    CS2Offsets,
+};
+
+/// Read the current value of mp_maxmoney.
+/// Returns None if the convar could not be resolved.
+pub fn read_max_money(cs2: &CS2Handle, offsets: &CS2Offsets) -> anyhow::Result<Option<i32>> {
+    let convar = match offsets.convar_max_money {
+        Some(convar) => convar,
+        None => return Ok(None),
+    };
+
+    Ok(Some(
+        cs2.read_sized::<i32>(&[convar, CConVarBaseData::VALUE])?,
+    ))
+}
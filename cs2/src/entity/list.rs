@@ -1,5 +1,9 @@
 use std::{
     collections::BTreeMap,
+    ops::{
+        Bound,
+        RangeBounds,
+    },
     sync::Arc,
 };
 
@@ -36,6 +40,33 @@ impl EntityList {
         &self.entities
     }
 
+    /// Returns all entities with an entity index within the given range.
+    pub fn entities_range(&self, range: impl RangeBounds<usize>) -> &[CEntityIdentity] {
+        let start = match range.start_bound() {
+            Bound::Included(value) => Bound::Included(*value as u32),
+            Bound::Excluded(value) => Bound::Excluded(*value as u32),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(value) => Bound::Included(*value as u32),
+            Bound::Excluded(value) => Bound::Excluded(*value as u32),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+
+        /* entities are stored ordered by their entity index */
+        let mut indices = self
+            .handle_lookup
+            .range((start, end))
+            .map(|(_, index)| *index);
+        let first = match indices.next() {
+            Some(index) => index,
+            None => return &[],
+        };
+        let last = indices.next_back().unwrap_or(first);
+
+        &self.entities[first..=last]
+    }
+
     pub fn lookup_entity_index(&self, entity_index: u32) -> Option<&CEntityIdentity> {
         self.handle_lookup
             .get(&entity_index)
@@ -6,3 +6,9 @@ pub use system::*;
 
 mod list;
 pub use list::*;
+
+mod pawn;
+pub use pawn::*;
+
+mod origin;
+pub use origin::*;
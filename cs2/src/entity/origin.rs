@@ -0,0 +1,150 @@
+use cs2_schema_generated::cs2::client::{
+    C_BaseEntity,
+    C_BasePlayerPawn,
+};
+
+/// Filter out origins which are not (yet) initialized.
+fn valid_origin(origin: [f32; 3]) -> Option<[f32; 3]> {
+    if origin.iter().all(|value| value.is_finite()) && origin != [0.0; 3] {
+        Some(origin)
+    } else {
+        None
+    }
+}
+
+/// Read the absolute origin of the entity.
+/// In contrast to reading the bone states this only reads the origin from the game scene node.
+///
+/// Returns None if the entity has no game scene node (e.g. while being created).
+pub fn read_origin(entity: &C_BaseEntity) -> anyhow::Result<Option<[f32; 3]>> {
+    match entity.m_pGameSceneNode()?.try_reference_schema()? {
+        Some(game_scene_node) => Ok(valid_origin(game_scene_node.m_vecAbsOrigin()?)),
+        None => Ok(None),
+    }
+}
+
+/// Read the absolute origin of the pawn.
+/// Falls back to the last networked origin if the game scene node is not yet available
+/// (e.g. during spawn or transition frames).
+pub fn read_pawn_origin(pawn: &C_BasePlayerPawn) -> anyhow::Result<Option<[f32; 3]>> {
+    if let Some(origin) = read_origin(pawn)? {
+        return Ok(Some(origin));
+    }
+
+    Ok(valid_origin(pawn.m_vOldOrigin()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use cs2_schema_declaration::{
+        SchemaValue,
+        VecMemoryDriver,
+    };
+    use cs2_schema_generated::cs2::client::{
+        C_BaseEntity,
+        C_BasePlayerPawn,
+    };
+
+    use super::{
+        read_origin,
+        read_pawn_origin,
+    };
+
+    const BASE_ADDRESS: u64 = 0x10000;
+    const BUFFER_SIZE: usize = 0x2000;
+
+    /* schema offsets */
+    const OFFSET_GAME_SCENE_NODE: usize = 0x310;
+    const OFFSET_ABS_ORIGIN: usize = 0xC8;
+    const OFFSET_OLD_ORIGIN: usize = 0x1214;
+
+    /// Offset of the game scene node within the test buffer
+    const SCENE_NODE: usize = 0x1800;
+
+    fn write_u64(buffer: &mut [u8], offset: usize, value: u64) {
+        buffer[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+    }
+
+    fn write_vector(buffer: &mut [u8], offset: usize, value: [f32; 3]) {
+        for (index, value) in value.iter().enumerate() {
+            let offset = offset + index * 4;
+            buffer[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+        }
+    }
+
+    /// Create an entity buffer with an optional game scene node
+    fn entity_buffer(scene_node_origin: Option<[f32; 3]>) -> Vec<u8> {
+        let mut buffer = vec![0u8; BUFFER_SIZE];
+        if let Some(origin) = scene_node_origin {
+            write_u64(
+                &mut buffer,
+                OFFSET_GAME_SCENE_NODE,
+                BASE_ADDRESS + SCENE_NODE as u64,
+            );
+            write_vector(&mut buffer, SCENE_NODE + OFFSET_ABS_ORIGIN, origin);
+        }
+
+        buffer
+    }
+
+    fn parse<T: SchemaValue>(buffer: Vec<u8>) -> T {
+        VecMemoryDriver::new(BASE_ADDRESS, buffer)
+            .into_handle()
+            .reference_schema::<T>(0)
+            .unwrap()
+    }
+
+    #[test]
+    fn origin_null_scene_node() {
+        let entity = parse::<C_BaseEntity>(entity_buffer(None));
+        assert_eq!(read_origin(&entity).unwrap(), None);
+    }
+
+    #[test]
+    fn origin_scene_node() {
+        let entity = parse::<C_BaseEntity>(entity_buffer(Some([1.0, -2.0, 3.5])));
+        assert_eq!(read_origin(&entity).unwrap(), Some([1.0, -2.0, 3.5]));
+    }
+
+    #[test]
+    fn origin_uninitialized_scene_node() {
+        let entity = parse::<C_BaseEntity>(entity_buffer(Some([0.0; 3])));
+        assert_eq!(read_origin(&entity).unwrap(), None);
+
+        let entity = parse::<C_BaseEntity>(entity_buffer(Some([f32::NAN, 0.0, 1.0])));
+        assert_eq!(read_origin(&entity).unwrap(), None);
+    }
+
+    #[test]
+    fn pawn_origin_prefers_scene_node() {
+        let mut buffer = entity_buffer(Some([1.0, 2.0, 3.0]));
+        write_vector(&mut buffer, OFFSET_OLD_ORIGIN, [4.0, 5.0, 6.0]);
+
+        let pawn = parse::<C_BasePlayerPawn>(buffer);
+        assert_eq!(read_pawn_origin(&pawn).unwrap(), Some([1.0, 2.0, 3.0]));
+    }
+
+    #[test]
+    fn pawn_origin_fallback_null_scene_node() {
+        let mut buffer = entity_buffer(None);
+        write_vector(&mut buffer, OFFSET_OLD_ORIGIN, [4.0, 5.0, 6.0]);
+
+        let pawn = parse::<C_BasePlayerPawn>(buffer);
+        assert_eq!(read_pawn_origin(&pawn).unwrap(), Some([4.0, 5.0, 6.0]));
+    }
+
+    #[test]
+    fn pawn_origin_invalid() {
+        let mut buffer = entity_buffer(None);
+        write_vector(&mut buffer, OFFSET_OLD_ORIGIN, [f32::INFINITY, 5.0, 6.0]);
+
+        let pawn = parse::<C_BasePlayerPawn>(buffer);
+        assert_eq!(read_pawn_origin(&pawn).unwrap(), None);
+
+        let mut buffer = entity_buffer(Some([0.0; 3]));
+        write_vector(&mut buffer, OFFSET_OLD_ORIGIN, [0.0; 3]);
+
+        let pawn = parse::<C_BasePlayerPawn>(buffer);
+        assert_eq!(read_pawn_origin(&pawn).unwrap(), None);
+    }
+}
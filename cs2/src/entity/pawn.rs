@@ -0,0 +1,229 @@
+use cs2_schema_declaration::Ptr;
+use cs2_schema_generated::cs2::{
+    client::{
+        CCSPlayerBase_CameraServices,
+        C_BasePlayerPawn,
+        C_BasePlayerWeapon,
+        C_CSPlayerPawn,
+    },
+    globals::CNetworkViewOffsetVector,
+};
+
+use crate::{
+    read_pawn_origin,
+    Globals,
+};
+
+/// Entity flag indicating the entity stands on the ground (FL_ONGROUND)
+pub const FL_ONGROUND: u32 = 1 << 0;
+
+/// Height of the eyes above the pawn origin while standing.
+/// Used when the view offset is not (yet) available.
+const EYE_HEIGHT: f32 = 64.0;
+
+trait CNetworkViewOffsetVectorEx {
+    fn vector(&self) -> anyhow::Result<[f32; 3]>;
+}
+
+impl CNetworkViewOffsetVectorEx for CNetworkViewOffsetVector {
+    /* The decoded value is the first member of each CNetworkedQuantizedFloat */
+    fn vector(&self) -> anyhow::Result<[f32; 3]> {
+        Ok([
+            self.memory.reference_schema(0x10)?,
+            self.memory.reference_schema(0x18)?,
+            self.memory.reference_schema(0x20)?,
+        ])
+    }
+}
+
+/// Read the eye position of the pawn (origin + view offset).
+/// The view offset changes while crouching.
+///
+/// Returns None if the pawn origin is not available.
+pub fn read_eye_position(pawn: &C_BasePlayerPawn) -> anyhow::Result<Option<[f32; 3]>> {
+    let origin = match read_pawn_origin(pawn)? {
+        Some(origin) => origin,
+        None => return Ok(None),
+    };
+
+    let view_offset = pawn.m_vecViewOffset()?.vector()?;
+    let view_offset_valid =
+        view_offset.iter().all(|value| value.is_finite()) && view_offset[2] > 0.0;
+    let view_offset = if view_offset_valid {
+        view_offset
+    } else {
+        [0.0, 0.0, EYE_HEIGHT]
+    };
+
+    Ok(Some([
+        origin[0] + view_offset[0],
+        origin[1] + view_offset[1],
+        origin[2] + view_offset[2],
+    ]))
+}
+
+pub trait CSPlayerPawnEx {
+    /// Returns false if the pawn is airborne (jumping or falling)
+    fn is_on_ground(&self) -> anyhow::Result<bool>;
+
+    /// Returns true if the pawn is currently zoomed in with a scoped weapon
+    fn is_scoped(&self) -> anyhow::Result<bool>;
+
+    /// Current field of view of the pawn.
+    /// Returns None if the pawn uses the default field of view.
+    fn fov(&self) -> anyhow::Result<Option<u32>>;
+
+    /// Eye position of the pawn.
+    /// Returns None if the pawn origin is not available.
+    fn eye_position(&self) -> anyhow::Result<Option<nalgebra::Vector3<f32>>>;
+
+    /// Coarse check if the pawn looks roughly towards the target.
+    /// This is not a line of sight check!
+    /// `max_angle` is the maximum angle (in degrees) between the view direction and the target.
+    fn is_looking_at(
+        &self,
+        target: &nalgebra::Vector3<f32>,
+        max_angle: f32,
+    ) -> anyhow::Result<bool>;
+}
+
+impl CSPlayerPawnEx for C_CSPlayerPawn {
+    fn is_on_ground(&self) -> anyhow::Result<bool> {
+        Ok((self.m_fFlags()? & FL_ONGROUND) != 0)
+    }
+
+    fn is_scoped(&self) -> anyhow::Result<bool> {
+        self.m_bIsScoped()
+    }
+
+    fn fov(&self) -> anyhow::Result<Option<u32>> {
+        let camera_services = match self
+            .m_pCameraServices()?
+            .cast::<CCSPlayerBase_CameraServices>()
+            .try_reference_schema()?
+        {
+            Some(camera_services) => camera_services,
+            None => return Ok(None),
+        };
+
+        /* a FOV of zero indicates the default FOV */
+        let fov = camera_services.m_iFOV()?;
+        Ok(if fov > 0 { Some(fov) } else { None })
+    }
+
+    fn eye_position(&self) -> anyhow::Result<Option<nalgebra::Vector3<f32>>> {
+        let position = read_eye_position(self)?;
+        Ok(position.map(|position| nalgebra::Vector3::from_column_slice(&position)))
+    }
+
+    fn is_looking_at(
+        &self,
+        target: &nalgebra::Vector3<f32>,
+        max_angle: f32,
+    ) -> anyhow::Result<bool> {
+        let eye_position = match self.eye_position()? {
+            Some(position) => position,
+            None => return Ok(false),
+        };
+
+        let eye_angles = self.m_angEyeAngles()?;
+        let angle = view_angle_to([eye_angles[0], eye_angles[1]], &eye_position, target);
+
+        Ok(angle <= max_angle)
+    }
+}
+
+/// Calculate the (normalized) forward direction from the view angles (pitch, yaw in degrees).
+pub fn view_direction(view_angles: [f32; 2]) -> nalgebra::Vector3<f32> {
+    let pitch = view_angles[0].to_radians();
+    let yaw = view_angles[1].to_radians();
+    nalgebra::Vector3::new(
+        pitch.cos() * yaw.cos(),
+        pitch.cos() * yaw.sin(),
+        -pitch.sin(),
+    )
+}
+
+/// Calculate the angle (in degrees) between the view direction (pitch, yaw)
+/// and the direction from the origin to the target.
+pub fn view_angle_to(
+    view_angles: [f32; 2],
+    origin: &nalgebra::Vector3<f32>,
+    target: &nalgebra::Vector3<f32>,
+) -> f32 {
+    let forward = view_direction(view_angles);
+    let direction = target - origin;
+    let distance = direction.norm();
+    if distance <= f32::EPSILON {
+        return 0.0;
+    }
+
+    (forward.dot(&direction) / distance)
+        .clamp(-1.0, 1.0)
+        .acos()
+        .to_degrees()
+}
+
+fn sanitize_angle(angle: [f32; 4]) -> [f32; 2] {
+    if angle[0].is_finite() && angle[1].is_finite() {
+        [angle[0], angle[1]]
+    } else {
+        [0.0, 0.0]
+    }
+}
+
+/// Read the accumulated recoil (aim punch) as pitch and yaw.
+/// Returns zeros if the pawn is null or has not yet been spawned.
+pub fn read_aim_punch(pawn: &Ptr<C_CSPlayerPawn>) -> anyhow::Result<[f32; 2]> {
+    match pawn.try_reference_schema()? {
+        Some(pawn) => Ok(sanitize_angle(pawn.m_aimPunchAngle()?)),
+        None => Ok([0.0, 0.0]),
+    }
+}
+
+/// Read the current aim punch velocity as pitch and yaw.
+/// Returns zeros if the pawn is null or has not yet been spawned.
+pub fn read_aim_punch_velocity(pawn: &Ptr<C_CSPlayerPawn>) -> anyhow::Result<[f32; 2]> {
+    match pawn.try_reference_schema()? {
+        Some(pawn) => Ok(sanitize_angle(pawn.m_aimPunchAngleVel()?)),
+        None => Ok([0.0, 0.0]),
+    }
+}
+
+/// Read the game time at which the active weapon of the pawn is able to fire again.
+/// Compare the result against `Globals::cur_time`.
+/// Returns None if the pawn has no active weapon.
+pub fn read_next_attack_time(
+    pawn: &C_CSPlayerPawn,
+    globals: &Globals,
+) -> anyhow::Result<Option<f32>> {
+    let weapon = match pawn
+        .m_pClippingWeapon()?
+        .cast::<C_BasePlayerWeapon>()
+        .try_reference_schema()?
+    {
+        Some(weapon) => weapon,
+        None => return Ok(None),
+    };
+
+    let next_attack_tick = weapon.m_nNextPrimaryAttackTick()?.m_Value()? as f32
+        + weapon.m_flNextPrimaryAttackTickRatio()?;
+    Ok(Some(next_attack_tick * globals.tick_interval()?))
+}
+
+/// Read the remaining time (in seconds) until the pawn is no longer flashed.
+/// Returns zero if the pawn is currently not flashed.
+pub fn read_flash_time_remaining(pawn: &C_CSPlayerPawn, globals: &Globals) -> anyhow::Result<f32> {
+    let flash_duration = pawn.m_flFlashDuration()?;
+    if !flash_duration.is_finite() || flash_duration <= 0.0 {
+        return Ok(0.0);
+    }
+
+    /* m_flFlashBangTime contains the game time at which the flash effect ends */
+    let remaining = pawn.m_flFlashBangTime()? - globals.cur_time()?;
+    if !remaining.is_finite() {
+        return Ok(0.0);
+    }
+
+    Ok(remaining.clamp(0.0, flash_duration))
+}
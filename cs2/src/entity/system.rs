@@ -3,6 +3,8 @@ use std::{
     ops::{
         Deref,
         DerefMut,
+        RangeBounds,
+        RangeInclusive,
     },
     sync::Arc,
 };
@@ -10,7 +12,6 @@ use std::{
 use anyhow::{
     Context,
     Ok,
-    Result,
 };
 use cs2_schema_declaration::{
     Ptr,
@@ -56,6 +57,9 @@ impl<T> DerefMut for TypedEntityIdentity<T> {
     }
 }
 
+/// Entity indices reserved for the player controllers
+pub const PLAYER_ENTITY_INDEX_RANGE: RangeInclusive<usize> = 1..=64;
+
 /// Helper class for CS2 global entity system
 pub struct EntitySystem {
     cs2: Arc<CS2Handle>,
@@ -88,6 +92,12 @@ impl EntitySystem {
         self.entity_list.entities()
     }
 
+    /// Returns all identities with an entity index within the given range.
+    /// Use `PLAYER_ENTITY_INDEX_RANGE` for player controllers only.
+    pub fn all_identities_range(&self, range: impl RangeBounds<usize>) -> &[CEntityIdentity] {
+        self.entity_list.entities_range(range)
+    }
+
     pub fn all_identities_of_class(
         &self,
         reference: &CEntityIdentity,
@@ -107,6 +117,29 @@ impl EntitySystem {
         return Ok(result);
     }
 
+    /// Read a single field of every given entity.
+    /// This avoids reading the whole schema class of each entity
+    /// if only one field (e.g. the health) is required.
+    /// See `CS2Handle::read_many` for how the reads are issued.
+    ///
+    /// Returns the entity index alongside the field value.
+    /// The value will be None if the field could not be read.
+    pub fn read_field_all<T: Copy>(
+        &self,
+        identities: &[CEntityIdentity],
+        field_offset: u64,
+    ) -> anyhow::Result<Vec<(u32, Option<T>)>> {
+        let mut entity_indices = Vec::with_capacity(identities.len());
+        let mut addresses = Vec::with_capacity(identities.len());
+        for identity in identities {
+            entity_indices.push(identity.handle::<()>()?.get_entity_index());
+            addresses.push(identity.entity_ptr::<()>()?.address()? + field_offset);
+        }
+
+        let values = self.cs2.read_many::<T>(&addresses)?;
+        Ok(entity_indices.into_iter().zip(values).collect())
+    }
+
     /// Returns the entity ptr
     pub fn get_by_handle<T: SchemaValue>(
         &self,
@@ -128,10 +161,17 @@ impl EntitySystem {
             .context("missing local player controller")?;
 
         let local_controller_identitiy = local_controller.m_pEntity()?.read_schema()?;
-        let identities = self.all_identities_of_class(&local_controller_identitiy)?;
-        Ok(identities
-            .into_iter()
-            .map(|identity| identity.entity_ptr())
-            .collect::<Result<Vec<_>>>()?)
+        let class_info = local_controller_identitiy.entity_class_info()?.address()?;
+
+        let mut result = Vec::with_capacity(64);
+        for identity in self.all_identities_range(PLAYER_ENTITY_INDEX_RANGE) {
+            if identity.entity_class_info()?.address()? != class_info {
+                continue;
+            }
+
+            result.push(identity.entity_ptr()?);
+        }
+
+        Ok(result)
     }
 }
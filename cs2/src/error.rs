@@ -0,0 +1,21 @@
+use std::time::Duration;
+
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum CS2Error {
+    #[error("the cs2 handle has been paused")]
+    Paused,
+
+    #[error("the cs2 handle has been shut down")]
+    ShutDown,
+
+    #[error("the polled condition has not been met within {0:?}")]
+    Timeout(Duration),
+
+    #[error("the read thread has been terminated")]
+    ReadThreadTerminated,
+
+    #[error("cpu {0} can not be used for the thread affinity")]
+    InvalidCpuAffinity(usize),
+}
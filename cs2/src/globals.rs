@@ -15,6 +15,7 @@ define_schema! {
     pub struct Globals[0x48] {
         pub time_1: f32 = 0x00,
         pub frame_count_1: u32 = 0x04,
+        pub frame_time: f32 = 0x08,
 
         pub max_player_count: u32 = 0x10,
 
@@ -25,3 +26,15 @@ define_schema! {
         pub two_tick_time: f32 = 0x44,
     }
 }
+
+impl Globals {
+    /// Current game time in seconds
+    pub fn cur_time(&self) -> anyhow::Result<f32> {
+        self.time_2()
+    }
+
+    /// Duration of a single server tick in seconds
+    pub fn tick_interval(&self) -> anyhow::Result<f32> {
+        Ok(self.two_tick_time()? / 2.0)
+    }
+}
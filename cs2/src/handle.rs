@@ -5,9 +5,17 @@ use std::{
     ffi::CStr,
     fmt::Debug,
     sync::{
+        atomic::{
+            AtomicBool,
+            Ordering,
+        },
         Arc,
         Weak,
     },
+    time::{
+        Duration,
+        Instant,
+    },
 };
 
 use anyhow::Context;
@@ -25,18 +33,29 @@ use valthrun_kernel_interface::{
         RequestProtectionToggle,
         ResponseCsModule,
     },
+    scan_pattern,
     CS2ModuleInfo,
     KInterfaceError,
     KernelInterface,
     KeyboardState,
     ModuleInfo,
     MouseState,
+    PatternMatch,
+    SearchPattern,
 };
 
 use crate::{
+    CS2Error,
+    LocalRegion,
+    ReadThread,
     Signature,
     SignatureType,
 };
+#[cfg(feature = "read-metrics")]
+use crate::{
+    ReadKind,
+    ReadMetrics,
+};
 
 pub struct CSMemoryDriver(Weak<CS2Handle>);
 impl MemoryDriver for CSMemoryDriver {
@@ -81,17 +100,61 @@ impl Module {
     }
 }
 
+/// Max size (in bytes) of a single contiguous read issued by `read_many`
+const READ_MANY_MAX_WINDOW_SIZE: u64 = 0x4000;
+
+/// Default size (in bytes) of a single window when scanning a module for a pattern
+const DEFAULT_PATTERN_SCAN_CHUNK_SIZE: usize = 16 * 1024 * 1024;
+
 /// Handle to the CS2 process
 pub struct CS2Handle {
     weak_self: Weak<Self>,
 
-    pub ke_interface: KernelInterface,
+    pub ke_interface: Arc<KernelInterface>,
     pub module_info: CS2ModuleInfo,
+
+    paused: AtomicBool,
+    protected: AtomicBool,
+    shut_down: AtomicBool,
+    read_thread: Option<ReadThread>,
+    pattern_scan_chunk_size: usize,
+
+    #[cfg(feature = "read-metrics")]
+    pub read_metrics: ReadMetrics,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct CreateOptions {
+    /// Name of the kernel driver device.
+    /// If not set the default device name will be used.
+    pub device_name: Option<String>,
+
+    /// Issue all memory reads from a dedicated worker thread.
+    pub dedicated_read_thread: bool,
+
+    /// Pin the dedicated read thread to the given cpu.
+    /// Only used when `dedicated_read_thread` is enabled.
+    pub cpu_affinity: Option<usize>,
+
+    /// Size (in bytes) of the windows a module will be split into when scanning for patterns.
+    /// If not set, 16 MiB windows will be used.
+    pub pattern_scan_chunk_size: Option<usize>,
 }
 
 impl CS2Handle {
     pub fn create() -> anyhow::Result<Arc<Self>> {
-        let interface = KernelInterface::create(obfstr!("\\\\.\\GLOBALROOT\\Device\\valthrun"))?;
+        Self::create_with_options(&Default::default())
+    }
+
+    pub fn create_with_options(options: &CreateOptions) -> anyhow::Result<Arc<Self>> {
+        let interface = match &options.device_name {
+            Some(device_name) => KernelInterface::create(&format!(
+                "{}{}",
+                obfstr!("\\\\.\\GLOBALROOT\\Device\\"),
+                device_name
+            ))?,
+            None => KernelInterface::create(obfstr!("\\\\.\\GLOBALROOT\\Device\\valthrun"))?,
+        };
 
         /*
          * Please no not analyze me:
@@ -127,22 +190,121 @@ impl CS2Handle {
             module_info.engine.module_size
         );
 
+        let interface = Arc::new(interface);
+        let read_thread = if options.dedicated_read_thread {
+            Some(ReadThread::spawn(interface.clone(), options.cpu_affinity)?)
+        } else {
+            None
+        };
+
         Ok(Arc::new_cyclic(|weak_self| Self {
             weak_self: weak_self.clone(),
 
             ke_interface: interface,
             module_info,
+
+            paused: AtomicBool::new(false),
+            protected: AtomicBool::new(true),
+            shut_down: AtomicBool::new(false),
+            read_thread,
+            pattern_scan_chunk_size: options
+                .pattern_scan_chunk_size
+                .unwrap_or(DEFAULT_PATTERN_SCAN_CHUNK_SIZE)
+                .max(1),
+
+            #[cfg(feature = "read-metrics")]
+            read_metrics: Default::default(),
         }))
     }
 
+    /// Pause all memory reads.
+    /// While paused every read will fail with `CS2Error::Paused` without issuing a kernel request.
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::Relaxed);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    fn ensure_not_paused(&self) -> anyhow::Result<()> {
+        if self.is_shut_down() {
+            Err(CS2Error::ShutDown.into())
+        } else if self.is_paused() {
+            Err(CS2Error::Paused.into())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Repeatedly invoke `f` until it yields a value.
+    /// Fails with `CS2Error::Timeout` if no value has been yielded within `timeout`.
+    /// Errors returned by `f` will be propagated immediately.
+    pub fn poll_until<T, F: Fn() -> anyhow::Result<Option<T>>>(
+        &self,
+        f: F,
+        interval: Duration,
+        timeout: Duration,
+    ) -> anyhow::Result<T> {
+        let start = Instant::now();
+        loop {
+            if let Some(value) = f()? {
+                return Ok(value);
+            }
+
+            let elapsed = start.elapsed();
+            if elapsed >= timeout {
+                return Err(CS2Error::Timeout(timeout).into());
+            }
+
+            std::thread::sleep(interval.min(timeout - elapsed));
+        }
+    }
+
     pub fn protect_process(&self) -> anyhow::Result<()> {
         unsafe {
             self.ke_interface
                 .execute_request(&RequestProtectionToggle { enabled: true })
         }?;
+        self.protected.store(true, Ordering::Relaxed);
         Ok(())
     }
 
+    pub fn is_shut_down(&self) -> bool {
+        self.shut_down.load(Ordering::Relaxed)
+    }
+
+    /// Disable the process protection and stop the dedicated read thread.
+    /// Every subsequent read will fail with `CS2Error::ShutDown`.
+    ///
+    /// Calling this multiple times is safe. It will also be invoked when the handle gets dropped.
+    pub fn shutdown(&self) {
+        if self.shut_down.swap(true, Ordering::Relaxed) {
+            /* already shut down */
+            return;
+        }
+
+        if let Some(read_thread) = &self.read_thread {
+            read_thread.shutdown();
+        }
+
+        if self.protected.swap(false, Ordering::Relaxed) {
+            let result = unsafe {
+                self.ke_interface
+                    .execute_request(&RequestProtectionToggle { enabled: false })
+            };
+            if let Err(error) = result {
+                log::warn!(
+                    "{}: {:#}",
+                    obfstr!("Failed to disable process protection"),
+                    error
+                );
+            }
+        }
+
+        log::debug!("{}", obfstr!("CS2 handle has been shut down"));
+    }
+
     pub fn send_keyboard_state(&self, states: &[KeyboardState]) -> anyhow::Result<()> {
         unsafe {
             self.ke_interface.execute_request(&RequestKeyboardState {
@@ -184,16 +346,188 @@ impl CS2Handle {
             + offset)
     }
 
+    /// Read raw memory either directly or via the dedicated read thread.
+    fn read_raw(&self, offsets: &[u64], buffer: &mut [u8]) -> anyhow::Result<()> {
+        let process_id = self.module_info.process_id;
+        let read_thread = match &self.read_thread {
+            Some(read_thread) => read_thread,
+            None => return Ok(self.ke_interface.read_slice(process_id, offsets, buffer)?),
+        };
+
+        let offsets = offsets.to_vec();
+        let length = buffer.len();
+        let result = read_thread.execute(move |interface| {
+            let mut buffer = vec![0u8; length];
+            interface
+                .read_slice(process_id, &offsets, &mut buffer)
+                .map(|_| buffer)
+                .map_err(anyhow::Error::from)
+        })??;
+
+        buffer.copy_from_slice(&result);
+        Ok(())
+    }
+
     pub fn read_sized<T: Copy>(&self, offsets: &[u64]) -> anyhow::Result<T> {
-        Ok(self
-            .ke_interface
-            .read(self.module_info.process_id, offsets)?)
+        self.ensure_not_paused()?;
+
+        #[cfg(feature = "read-metrics")]
+        let _metrics = self.read_metrics.record(ReadKind::Read);
+
+        let mut result = unsafe { std::mem::zeroed::<T>() };
+        let result_buff = unsafe {
+            std::slice::from_raw_parts_mut(
+                &mut result as *mut T as *mut u8,
+                std::mem::size_of::<T>(),
+            )
+        };
+
+        self.read_raw(offsets, result_buff)?;
+        Ok(result)
+    }
+
+    /// Read one value from every address.
+    /// Reads which failed (e.g. invalid addresses) will be None.
+    ///
+    /// Addresses close to each other (e.g. fields of entities which have been allocated next to each other)
+    /// will be read with one contiguous kernel read. All other addresses require a kernel read each.
+    pub fn read_many<T: Copy>(&self, addresses: &[u64]) -> anyhow::Result<Vec<Option<T>>> {
+        self.ensure_not_paused()?;
+
+        let value_size = std::mem::size_of::<T>() as u64;
+        let parse_value = |buffer: &[u8], offset: u64| unsafe {
+            std::ptr::read_unaligned(buffer[offset as usize..].as_ptr() as *const T)
+        };
+
+        let mut order = (0..addresses.len()).collect::<Vec<_>>();
+        order.sort_by_key(|index| addresses[*index]);
+
+        let mut result = vec![None; addresses.len()];
+        let mut window_start = 0;
+        while window_start < order.len() {
+            let start_address = addresses[order[window_start]];
+
+            let mut window_end = window_start + 1;
+            while window_end < order.len()
+                && (addresses[order[window_end]] - start_address).saturating_add(value_size)
+                    <= READ_MANY_MAX_WINDOW_SIZE
+            {
+                window_end += 1;
+            }
+
+            let window = &order[window_start..window_end];
+            window_start = window_end;
+
+            let window_size = addresses[window[window.len() - 1]] - start_address + value_size;
+            let mut buffer = vec![0u8; window_size as usize];
+            if self
+                .read_many_window(start_address, &mut buffer, window.len())
+                .is_ok()
+            {
+                for index in window {
+                    result[*index] = Some(parse_value(&buffer, addresses[*index] - start_address));
+                }
+                continue;
+            }
+
+            if window.len() == 1 {
+                continue;
+            }
+
+            /* parts of the window might be invalid, read every value on its own */
+            for index in window {
+                let mut buffer = vec![0u8; value_size as usize];
+                if self
+                    .read_many_window(addresses[*index], &mut buffer, 1)
+                    .is_ok()
+                {
+                    result[*index] = Some(parse_value(&buffer, 0));
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn read_many_window(
+        &self,
+        address: u64,
+        buffer: &mut [u8],
+        _value_count: usize,
+    ) -> anyhow::Result<()> {
+        #[cfg(feature = "read-metrics")]
+        let _metrics = self.read_metrics.record(if _value_count > 1 {
+            ReadKind::ReadSlice
+        } else {
+            ReadKind::Read
+        });
+
+        self.read_raw(&[address], buffer)
+    }
+
+    /// Read a value relative to the module base and return the
+    /// absolute address the value has been read from alongside with the value.
+    ///
+    /// The offsets will be resolved the same way as `read_sized` resolves them
+    /// except that the first offset is relative to the module base.
+    pub fn read_at<T: Copy>(&self, module: Module, offsets: &[u64]) -> anyhow::Result<(u64, T)> {
+        let (first_offset, offsets) = offsets.split_first().context("missing offsets")?;
+
+        let mut address = self.memory_address(module, *first_offset)?;
+        if let Some((last_offset, offsets)) = offsets.split_last() {
+            let mut chain = Vec::with_capacity(offsets.len() + 1);
+            chain.push(address);
+            chain.extend_from_slice(offsets);
+
+            address = self.read_sized::<u64>(&chain)? + last_offset;
+        }
+
+        let value = self.read_sized::<T>(&[address])?;
+        Ok((address, value))
+    }
+
+    /// Same as `read_at` but every offset might be negative
+    /// (e.g. to access a field located before the resolved pointer).
+    ///
+    /// Every pointer in the chain will be dereferenced on its own.
+    /// Fails if an address under- or overflows or the first address would be below the module base.
+    pub fn read_at_signed<T: Copy>(
+        &self,
+        module: Module,
+        offsets: &[i64],
+    ) -> anyhow::Result<(u64, T)> {
+        let (first_offset, offsets) = offsets.split_first().context("missing offsets")?;
+
+        let module_base = self.memory_address(module, 0)?;
+        let mut address = module_base
+            .checked_add_signed(*first_offset)
+            .filter(|address| *address >= module_base)
+            .with_context(|| format!("offset {} is outside of the module", first_offset))?;
+
+        for offset in offsets {
+            let base = self.read_sized::<u64>(&[address])?;
+            address = base
+                .checked_add_signed(*offset)
+                .with_context(|| format!("offset {} overflows address {:X}", offset, base))?;
+        }
+
+        let value = self.read_sized::<T>(&[address])?;
+        Ok((address, value))
     }
 
     pub fn read_slice<T: Copy>(&self, offsets: &[u64], buffer: &mut [T]) -> anyhow::Result<()> {
-        Ok(self
-            .ke_interface
-            .read_slice(self.module_info.process_id, offsets, buffer)?)
+        self.ensure_not_paused()?;
+
+        #[cfg(feature = "read-metrics")]
+        let _metrics = self.read_metrics.record(ReadKind::ReadSlice);
+
+        let buffer = unsafe {
+            std::slice::from_raw_parts_mut(
+                buffer.as_mut_ptr() as *mut u8,
+                std::mem::size_of_val(buffer),
+            )
+        };
+        self.read_raw(offsets, buffer)
     }
 
     pub fn read_string(
@@ -218,6 +552,14 @@ impl CS2Handle {
         }
     }
 
+    /// Read the whole memory region once and return a local copy of it.
+    /// Use this if many fields of an arbitrary layout should be read.
+    pub fn snapshot_region(&self, address: u64, size: usize) -> anyhow::Result<LocalRegion> {
+        let mut buffer = vec![0u8; size];
+        self.read_slice(&[address], buffer.as_mut_slice())?;
+        Ok(LocalRegion::new(address, buffer))
+    }
+
     fn create_memory_driver(&self) -> Arc<dyn MemoryDriver> {
         Arc::new(CSMemoryDriver(self.weak_self.clone())) as Arc<(dyn MemoryDriver + 'static)>
     }
@@ -256,21 +598,90 @@ impl CS2Handle {
         ))
     }
 
+    /// Search the pattern within the module and capture `context` bytes around the match.
+    /// The match offset is relative to the module base.
+    pub fn find_pattern_ex(
+        &self,
+        module: Module,
+        pattern: &dyn SearchPattern,
+        context: usize,
+    ) -> anyhow::Result<Option<PatternMatch>> {
+        self.ensure_not_paused()?;
+
+        let module_info = module
+            .get_base_offset(&self.module_info)
+            .context("invalid module")?;
+
+        #[cfg(feature = "read-metrics")]
+        let _metrics = self.read_metrics.record(ReadKind::FindPattern);
+
+        self.find_pattern_in(module_info, pattern, context)
+    }
+
+    fn find_pattern_in(
+        &self,
+        module_info: &ModuleInfo,
+        pattern: &dyn SearchPattern,
+        context: usize,
+    ) -> anyhow::Result<Option<PatternMatch>> {
+        if pattern.length() > module_info.module_size {
+            return Ok(None);
+        }
+
+        let module_base = module_info.base_address as u64;
+        let module_size = module_info.module_size;
+
+        /*
+         * Scan the module in windows of `pattern_scan_chunk_size` possible match starts.
+         * Every window overlaps with its neighbours by the pattern length (and the requested context)
+         * so matches straddling a window boundary will still be found.
+         */
+        let mut chunk_start = 0;
+        while chunk_start + pattern.length() <= module_size {
+            let chunk_end = (chunk_start + self.pattern_scan_chunk_size).min(module_size);
+
+            let window_start = chunk_start.saturating_sub(context);
+            let window_end = (chunk_end + pattern.length() - 1 + context).min(module_size);
+
+            let mut buffer = vec![0u8; window_end - window_start];
+            self.read_raw(&[module_base + window_start as u64], &mut buffer)?;
+
+            if let Some(mut pattern_match) =
+                scan_pattern(&buffer, module_base + window_start as u64, pattern, context)
+            {
+                let match_offset = (pattern_match.address - module_base) as usize;
+                if match_offset < chunk_end {
+                    pattern_match.offset = match_offset as u64;
+                    return Ok(Some(pattern_match));
+                }
+
+                /* match starts within the next window and will be found (with its full context) there */
+            }
+
+            chunk_start = chunk_end;
+        }
+
+        Ok(None)
+    }
+
     pub fn resolve_signature(&self, module: Module, signature: &Signature) -> anyhow::Result<u64> {
+        self.ensure_not_paused()?;
+
         log::trace!("Resolving '{}' in {:?}", signature.debug_name, module);
         let module_info = module
             .get_base_offset(&self.module_info)
             .context("invalid module")?;
 
+        #[cfg(feature = "read-metrics")]
+        let metrics = self.read_metrics.record(ReadKind::FindPattern);
+
         let inst_offset = self
-            .ke_interface
-            .find_pattern(
-                self.module_info.process_id,
-                module_info.base_address as u64,
-                module_info.module_size,
-                &*signature.pattern,
-            )?
-            .context("failed to find pattern")?;
+            .find_pattern_in(module_info, &*signature.pattern, 0)?
+            .context("failed to find pattern")?
+            .address;
+
+        #[cfg(feature = "read-metrics")]
+        drop(metrics);
 
         let value = self.reference_schema::<u32>(&[inst_offset + signature.offset])? as u64;
         let value = match &signature.value_type {
@@ -293,3 +704,9 @@ impl CS2Handle {
         Ok(value)
     }
 }
+
+impl Drop for CS2Handle {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
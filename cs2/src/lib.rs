@@ -1,6 +1,9 @@
 mod handle;
 pub use handle::*;
 
+mod error;
+pub use error::*;
+
 mod entity;
 pub use entity::*;
 
@@ -21,5 +24,25 @@ pub use model::*;
 mod globals;
 pub use globals::*;
 
+mod region;
+pub use region::*;
+
+mod connection;
+pub use connection::*;
+
+mod convar;
+pub use convar::*;
+
 mod signature;
 pub use signature::*;
+
+mod read_thread;
+pub use read_thread::*;
+
+mod self_test;
+pub use self_test::*;
+
+#[cfg(feature = "read-metrics")]
+mod metrics;
+#[cfg(feature = "read-metrics")]
+pub use metrics::*;
@@ -0,0 +1,108 @@
+use std::{
+    sync::atomic::{
+        AtomicU64,
+        AtomicUsize,
+        Ordering,
+    },
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+#[derive(Debug, Clone, Copy)]
+pub enum ReadKind {
+    Read,
+    ReadSlice,
+    FindPattern,
+}
+
+#[derive(Default)]
+struct ReadCounter {
+    calls: AtomicUsize,
+    duration_ns: AtomicU64,
+}
+
+impl ReadCounter {
+    fn take(&self) -> ReadCounterSnapshot {
+        ReadCounterSnapshot {
+            calls: self.calls.swap(0, Ordering::Relaxed),
+            duration: Duration::from_nanos(self.duration_ns.swap(0, Ordering::Relaxed)),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ReadCounterSnapshot {
+    pub calls: usize,
+    pub duration: Duration,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ReadMetricsSnapshot {
+    pub read: ReadCounterSnapshot,
+    pub read_slice: ReadCounterSnapshot,
+    pub find_pattern: ReadCounterSnapshot,
+}
+
+impl ReadMetricsSnapshot {
+    pub fn total_calls(&self) -> usize {
+        self.read.calls + self.read_slice.calls + self.find_pattern.calls
+    }
+
+    pub fn total_duration(&self) -> Duration {
+        self.read.duration + self.read_slice.duration + self.find_pattern.duration
+    }
+}
+
+/// Accumulates the amount and duration of the read requests
+/// issued by the CS2 handle.
+#[derive(Default)]
+pub struct ReadMetrics {
+    read: ReadCounter,
+    read_slice: ReadCounter,
+    find_pattern: ReadCounter,
+}
+
+impl ReadMetrics {
+    fn counter(&self, kind: ReadKind) -> &ReadCounter {
+        match kind {
+            ReadKind::Read => &self.read,
+            ReadKind::ReadSlice => &self.read_slice,
+            ReadKind::FindPattern => &self.find_pattern,
+        }
+    }
+
+    /// Record a read call. The duration will be accounted
+    /// when the returned guard gets dropped.
+    pub fn record(&self, kind: ReadKind) -> ReadMetricsGuard<'_> {
+        ReadMetricsGuard {
+            counter: self.counter(kind),
+            timestamp: Instant::now(),
+        }
+    }
+
+    /// Returns the collected metrics and resets all counters.
+    pub fn snapshot(&self) -> ReadMetricsSnapshot {
+        ReadMetricsSnapshot {
+            read: self.read.take(),
+            read_slice: self.read_slice.take(),
+            find_pattern: self.find_pattern.take(),
+        }
+    }
+}
+
+pub struct ReadMetricsGuard<'a> {
+    counter: &'a ReadCounter,
+    timestamp: Instant,
+}
+
+impl Drop for ReadMetricsGuard<'_> {
+    fn drop(&mut self) {
+        self.counter.calls.fetch_add(1, Ordering::Relaxed);
+        self.counter.duration_ns.fetch_add(
+            self.timestamp.elapsed().as_nanos() as u64,
+            Ordering::Relaxed,
+        );
+    }
+}
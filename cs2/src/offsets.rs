@@ -2,6 +2,7 @@ use anyhow::Context;
 use obfstr::obfstr;
 
 use crate::{
+    offsets_manual::tier0::CConVarBaseData,
     CS2Handle,
     Module,
     Signature,
@@ -24,6 +25,18 @@ pub struct CS2Offsets {
 
     /// Offset for the crosshair entity id in C_CSPlayerPawn
     pub offset_crosshair_id: u64,
+
+    /// Address for the network game client ptr.
+    /// None if the signature could not be resolved.
+    pub network_game_client: Option<u64>,
+
+    /// Offset for the signon state in CNetworkGameClient.
+    /// None if the signature could not be resolved.
+    pub offset_signon_state: Option<u64>,
+
+    /// Address for the mp_maxmoney convar data ptr.
+    /// None if the signature could not be resolved.
+    pub convar_max_money: Option<u64>,
 }
 
 impl CS2Offsets {
@@ -38,9 +51,37 @@ impl CS2Offsets {
                 .with_context(|| obfstr!("view matrix").to_string())?,
             offset_crosshair_id: Self::find_offset_crosshair_id(cs2)
                 .with_context(|| obfstr!("crosshair id").to_string())?,
+            /* the connection state is optional, hence failing to resolve these is not fatal */
+            network_game_client: Self::resolve_optional(
+                obfstr!("network game client"),
+                Self::find_network_game_client(cs2),
+            ),
+            offset_signon_state: Self::resolve_optional(
+                obfstr!("signon state"),
+                Self::find_offset_signon_state(cs2),
+            ),
+            convar_max_money: Self::resolve_optional(
+                obfstr!("mp_maxmoney convar"),
+                Self::find_convar_max_money(cs2),
+            ),
         })
     }
 
+    fn resolve_optional(name: &str, result: anyhow::Result<u64>) -> Option<u64> {
+        match result {
+            Ok(value) => Some(value),
+            Err(error) => {
+                log::warn!(
+                    "{} {}: {:#}",
+                    obfstr!("Failed to resolve optional offset"),
+                    name,
+                    error
+                );
+                None
+            }
+        }
+    }
+
     fn find_globals(cs2: &CS2Handle) -> anyhow::Result<u64> {
         cs2.resolve_signature(
             Module::Client,
@@ -101,4 +142,47 @@ impl CS2Offsets {
             ),
         )
     }
+
+    fn find_network_game_client(cs2: &CS2Handle) -> anyhow::Result<u64> {
+        cs2.resolve_signature(
+            Module::Engine,
+            &Signature::relative_address(
+                obfstr!("network game client"),
+                obfstr!("48 89 3D ? ? ? ? 48 8D 15"),
+                0x03,
+                0x07,
+            ),
+        )
+    }
+
+    fn find_offset_signon_state(cs2: &CS2Handle) -> anyhow::Result<u64> {
+        cs2.resolve_signature(
+            Module::Engine,
+            &Signature::offset(
+                obfstr!("CNetworkGameClient signon state"),
+                obfstr!("44 8B 81 ? ? ? ? 48 8D 0D"),
+                0x03,
+            ),
+        )
+    }
+
+    fn find_convar_max_money(cs2: &CS2Handle) -> anyhow::Result<u64> {
+        let address = cs2.resolve_signature(
+            Module::Client,
+            &Signature::relative_address(
+                obfstr!("mp_maxmoney convar"),
+                obfstr!("48 8B 05 ? ? ? ? 8B 40 40 89 83"),
+                0x03,
+                0x07,
+            ),
+        )?;
+
+        /* the pattern might match another convar after a game update */
+        let name = cs2.read_string(&[address, CConVarBaseData::NAME, 0], Some(16))?;
+        if name != obfstr!("mp_maxmoney") {
+            anyhow::bail!("{} {}", obfstr!("unexpected convar"), name);
+        }
+
+        Ok(address)
+    }
 }
@@ -15,3 +15,14 @@ pub mod client {
         pub const BONE_PARENT: u64 = 0x178;
     }
 }
+
+pub mod tier0 {
+    #[allow(non_snake_case)]
+    pub mod CConVarBaseData {
+        /* Offset is *const i8 */
+        pub const NAME: u64 = 0x00;
+
+        /* Offset is the current value, for integer convars an i32 */
+        pub const VALUE: u64 = 0x40;
+    }
+}
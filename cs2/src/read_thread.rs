@@ -0,0 +1,98 @@
+use std::{
+    sync::{
+        mpsc,
+        Arc,
+        Mutex,
+    },
+    thread::JoinHandle,
+};
+
+use valthrun_kernel_interface::KernelInterface;
+use windows::Win32::System::Threading::{
+    GetCurrentThread,
+    SetThreadAffinityMask,
+};
+
+use crate::CS2Error;
+
+type ReadJob = Box<dyn FnOnce(&KernelInterface) + Send>;
+
+/// Dedicated worker thread which issues the kernel requests.
+/// All jobs will be executed sequentially in the order they've been submitted.
+pub struct ReadThread {
+    sender: Mutex<Option<mpsc::Sender<ReadJob>>>,
+    thread: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl ReadThread {
+    pub fn spawn(
+        interface: Arc<KernelInterface>,
+        cpu_affinity: Option<usize>,
+    ) -> anyhow::Result<Self> {
+        if let Some(cpu) = cpu_affinity {
+            /* the affinity mask only has one bit per cpu */
+            if cpu >= usize::BITS as usize {
+                return Err(CS2Error::InvalidCpuAffinity(cpu).into());
+            }
+        }
+
+        let (sender, receiver) = mpsc::channel::<ReadJob>();
+        let thread = std::thread::Builder::new()
+            .name("cs2-read".to_string())
+            .spawn(move || {
+                if let Some(cpu) = cpu_affinity {
+                    let result =
+                        unsafe { SetThreadAffinityMask(GetCurrentThread(), 1usize << cpu) };
+                    if result == 0 {
+                        log::warn!("Failed to pin the read thread to cpu {}", cpu);
+                    }
+                }
+
+                while let Ok(job) = receiver.recv() {
+                    job(&interface);
+                }
+            })?;
+
+        Ok(Self {
+            sender: Mutex::new(Some(sender)),
+            thread: Mutex::new(Some(thread)),
+        })
+    }
+
+    /// Execute the job on the read thread and wait for its result.
+    pub fn execute<R: Send + 'static>(
+        &self,
+        job: impl FnOnce(&KernelInterface) -> R + Send + 'static,
+    ) -> anyhow::Result<R> {
+        let (result_sender, result_receiver) = mpsc::sync_channel(1);
+        {
+            let sender = self.sender.lock().unwrap();
+            let sender = sender.as_ref().ok_or(CS2Error::ReadThreadTerminated)?;
+            sender
+                .send(Box::new(move |interface| {
+                    let _ = result_sender.send(job(interface));
+                }))
+                .map_err(|_| CS2Error::ReadThreadTerminated)?;
+        }
+
+        Ok(result_receiver
+            .recv()
+            .map_err(|_| CS2Error::ReadThreadTerminated)?)
+    }
+
+    /// Terminate the worker thread after all pending jobs have been executed.
+    /// Subsequent jobs will fail with `CS2Error::ReadThreadTerminated`.
+    pub fn shutdown(&self) {
+        /* dropping the sender terminates the worker loop */
+        self.sender.lock().unwrap().take();
+        if let Some(thread) = self.thread.lock().unwrap().take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for ReadThread {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use cs2_schema_declaration::{
+    MemoryDriver,
+    MemoryHandle,
+    SchemaValue,
+    VecMemoryDriver,
+};
+
+/// Local copy of a memory region of the CS2 process.
+/// All accessors parse the local copy and do not issue any further kernel reads.
+pub struct LocalRegion {
+    driver: Arc<VecMemoryDriver>,
+}
+
+impl LocalRegion {
+    pub fn new(address: u64, buffer: Vec<u8>) -> Self {
+        Self {
+            driver: Arc::new(VecMemoryDriver::new(address, buffer)),
+        }
+    }
+
+    /// Address of the region within the CS2 process
+    pub fn address(&self) -> u64 {
+        self.driver.base_address()
+    }
+
+    pub fn len(&self) -> usize {
+        self.driver.buffer().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.driver.buffer().is_empty()
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.driver.buffer()
+    }
+
+    fn bytes_at(&self, offset: u64, length: usize) -> anyhow::Result<&[u8]> {
+        let buffer = self.driver.buffer();
+        let offset = offset as usize;
+        match offset.checked_add(length) {
+            Some(end) if end <= buffer.len() => Ok(&buffer[offset..end]),
+            _ => anyhow::bail!(
+                "offset {:X} (+{:X}) exceeds the region size of {:X}",
+                offset,
+                length,
+                buffer.len()
+            ),
+        }
+    }
+
+    /// Parse a value at the given offset relative to the region start.
+    pub fn get<T: Copy>(&self, offset: u64) -> anyhow::Result<T> {
+        let bytes = self.bytes_at(offset, std::mem::size_of::<T>())?;
+        Ok(unsafe { std::ptr::read_unaligned(bytes.as_ptr() as *const T) })
+    }
+
+    /// Parse `count` consecutive values at the given offset relative to the region start.
+    pub fn get_slice<T: Copy>(&self, offset: u64, count: usize) -> anyhow::Result<Vec<T>> {
+        let element_size = std::mem::size_of::<T>();
+        let bytes = self.bytes_at(offset, element_size * count)?;
+        Ok((0..count)
+            .map(|index| unsafe {
+                std::ptr::read_unaligned(bytes[index * element_size..].as_ptr() as *const T)
+            })
+            .collect())
+    }
+
+    /// Wrap a schema class around the local copy at the given offset.
+    /// Member accessors outside of the region will fail.
+    pub fn reference_schema<T: SchemaValue>(&self, offset: u64) -> anyhow::Result<T> {
+        let driver: Arc<dyn MemoryDriver> = self.driver.clone();
+        MemoryHandle::from_driver(&driver, self.driver.base_address()).reference_schema(offset)
+    }
+}
@@ -0,0 +1,150 @@
+use std::fmt::Display;
+
+use obfstr::obfstr;
+
+use crate::{
+    CS2Handle,
+    CS2Offsets,
+    Globals,
+    Module,
+};
+
+/// Result of a single self test check
+#[derive(Debug, Clone)]
+pub struct SelfTestCheck {
+    pub name: String,
+
+    /// Details about the check result or the error message if the check failed
+    pub result: Result<String, String>,
+}
+
+impl SelfTestCheck {
+    fn new(name: &str, result: anyhow::Result<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            result: result.map_err(|error| format!("{:#}", error)),
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+/// Structured report of the CS2 handle self test
+#[derive(Debug, Clone, Default)]
+pub struct SelfTestReport {
+    pub checks: Vec<SelfTestCheck>,
+}
+
+impl SelfTestReport {
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(SelfTestCheck::passed)
+    }
+}
+
+impl Display for SelfTestReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for check in self.checks.iter() {
+            match &check.result {
+                Ok(details) => writeln!(f, "[PASS] {}: {}", check.name, details)?,
+                Err(error) => writeln!(f, "[FAIL] {}: {}", check.name, error)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl CS2Handle {
+    /// Verify the driver, module info, pattern scanning and a sample read.
+    /// Failing checks will not abort the self test.
+    pub fn self_test(&self) -> SelfTestReport {
+        let mut report = SelfTestReport::default();
+
+        report.checks.push(SelfTestCheck::new(
+            obfstr!("driver"),
+            self.self_test_driver(),
+        ));
+        report.checks.push(SelfTestCheck::new(
+            obfstr!("modules"),
+            self.self_test_modules(),
+        ));
+
+        let offsets = CS2Offsets::resolve_offsets(self);
+        let globals = match &offsets {
+            Ok(offsets) => self.self_test_globals(offsets),
+            Err(_) => Err(anyhow::anyhow!("offsets unavailable")),
+        };
+        report.checks.push(SelfTestCheck::new(
+            obfstr!("signatures"),
+            offsets.map(|_| "all signatures resolved".to_string()),
+        ));
+        report
+            .checks
+            .push(SelfTestCheck::new(obfstr!("globals"), globals));
+
+        report
+    }
+
+    fn self_test_driver(&self) -> anyhow::Result<String> {
+        let client_base = self.memory_address(Module::Client, 0)?;
+        let header = self.read_sized::<[u8; 2]>(&[client_base])?;
+        if &header != b"MZ" {
+            anyhow::bail!("unexpected client.dll header {:02X?}", header);
+        }
+
+        Ok(format!(
+            "driver version {:X}, process id {}",
+            self.ke_interface.driver_version(),
+            self.module_info.process_id
+        ))
+    }
+
+    fn self_test_modules(&self) -> anyhow::Result<String> {
+        let modules = [
+            (obfstr!("client.dll").to_string(), &self.module_info.client),
+            (obfstr!("engine2.dll").to_string(), &self.module_info.engine),
+            (
+                obfstr!("schemasystem.dll").to_string(),
+                &self.module_info.schemasystem,
+            ),
+        ];
+
+        for (name, module) in modules.iter() {
+            if module.base_address == 0 || module.module_size == 0 {
+                anyhow::bail!("{} has an invalid base address or size", name);
+            }
+        }
+
+        Ok(modules
+            .iter()
+            .map(|(name, module)| {
+                format!(
+                    "{} at {:X} ({:X} bytes)",
+                    name, module.base_address, module.module_size
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", "))
+    }
+
+    fn self_test_globals(&self, offsets: &CS2Offsets) -> anyhow::Result<String> {
+        let globals = self.reference_schema::<Globals>(&[offsets.globals, 0])?;
+
+        let max_player_count = globals.max_player_count()?;
+        if max_player_count > 128 {
+            anyhow::bail!("implausible max player count {}", max_player_count);
+        }
+
+        let cur_time = globals.cur_time()?;
+        if !cur_time.is_finite() || cur_time < 0.0 {
+            anyhow::bail!("implausible current time {}", cur_time);
+        }
+
+        Ok(format!(
+            "max player count {}, current time {:.2}",
+            max_player_count, cur_time
+        ))
+    }
+}
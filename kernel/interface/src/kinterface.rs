@@ -46,6 +46,54 @@ use crate::{
     SearchPattern,
 };
 
+/// Result of a pattern search including the surrounding bytes
+#[derive(Debug, Clone)]
+pub struct PatternMatch {
+    /// Absolute address of the match
+    pub address: u64,
+
+    /// Offset of the match relative to the search start
+    pub offset: u64,
+
+    /// Bytes in front of the match (might be less than requested at the start of the search region)
+    pub context_before: Vec<u8>,
+    pub matched: Vec<u8>,
+
+    /// Bytes after the match (might be less than requested at the end of the search region)
+    pub context_after: Vec<u8>,
+}
+
+/// Search the pattern within the buffer and capture up to `context` bytes before and after the match.
+/// `address` is the address of the first byte within the buffer.
+pub fn scan_pattern(
+    buffer: &[u8],
+    address: u64,
+    pattern: &dyn SearchPattern,
+    context: usize,
+) -> Option<PatternMatch> {
+    if pattern.length() > buffer.len() {
+        return None;
+    }
+
+    for (index, window) in buffer.windows(pattern.length()).enumerate() {
+        if !pattern.is_matching(window) {
+            continue;
+        }
+
+        let match_end = index + pattern.length();
+        return Some(PatternMatch {
+            address: address + index as u64,
+            offset: index as u64,
+
+            context_before: buffer[index.saturating_sub(context)..index].to_vec(),
+            matched: window.to_vec(),
+            context_after: buffer[match_end..(match_end + context).min(buffer.len())].to_vec(),
+        });
+    }
+
+    None
+}
+
 /// Interface for our kernel driver
 pub struct KernelInterface {
     driver_handle: Foundation::HANDLE,
@@ -282,6 +330,20 @@ impl KernelInterface {
         length: usize,
         pattern: &dyn SearchPattern,
     ) -> KResult<Option<u64>> {
+        Ok(self
+            .find_pattern_ex(process_id, address, length, pattern, 0)?
+            .map(|result| result.address))
+    }
+
+    /// Find the pattern and capture up to `context` bytes before and after the match.
+    pub fn find_pattern_ex(
+        &self,
+        process_id: i32,
+        address: u64,
+        length: usize,
+        pattern: &dyn SearchPattern,
+        context: usize,
+    ) -> KResult<Option<PatternMatch>> {
         if pattern.length() > length {
             return Ok(None);
         }
@@ -290,15 +352,7 @@ impl KernelInterface {
         buffer.resize(length, 0);
         self.read_slice(process_id, &[address], &mut buffer)?;
 
-        for (index, window) in buffer.windows(pattern.length()).enumerate() {
-            if !pattern.is_matching(window) {
-                continue;
-            }
-
-            return Ok(Some(address + index as u64));
-        }
-
-        Ok(None)
+        Ok(scan_pattern(&buffer, address, pattern, context))
     }
 
     pub fn add_metrics_record(&self, record_type: &str, record_payload: &str) -> KResult<()> {
@@ -665,4 +665,8 @@ impl SystemRuntimeController {
     pub fn debug_overlay_shown(&self) -> bool {
         self.debug_overlay_shown
     }
+
+    pub fn target_window_focused(&self) -> bool {
+        self.window_tracker.is_target_focused()
+    }
 }
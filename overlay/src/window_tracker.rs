@@ -21,6 +21,7 @@ use windows::{
                 FindWindowExA,
                 FindWindowW,
                 GetClientRect,
+                GetForegroundWindow,
                 GetWindowRect,
                 GetWindowThreadProcessId,
                 MoveWindow,
@@ -114,6 +115,7 @@ impl OverlayTarget {
 pub struct WindowTracker {
     cs2_hwnd: HWND,
     current_bounds: RECT,
+    target_focused: bool,
 }
 
 impl WindowTracker {
@@ -126,9 +128,15 @@ impl WindowTracker {
         Ok(Self {
             cs2_hwnd: hwnd,
             current_bounds: Default::default(),
+            target_focused: true,
         })
     }
 
+    /// Returns true if either CS2 or the overlay itself is the foreground window.
+    pub fn is_target_focused(&self) -> bool {
+        self.target_focused
+    }
+
     pub fn mark_force_update(&mut self) {
         self.current_bounds = Default::default();
     }
@@ -151,6 +159,10 @@ impl WindowTracker {
             ClientToScreen(self.cs2_hwnd, &mut rect.right as *mut _ as *mut POINT);
         }
 
+        let foreground_hwnd = unsafe { GetForegroundWindow() };
+        self.target_focused =
+            foreground_hwnd == self.cs2_hwnd || foreground_hwnd == HWND(overlay.hwnd());
+
         if unsafe { GetFocus() } != self.cs2_hwnd {
             /*
              * CS2 will render a black screen as soon as CS2 does not have the focus and is completely covered by